@@ -0,0 +1,135 @@
+//! The path-safe `1/<type>/<hex>` form made by [`to_ascii`](crate::to_ascii), with optional
+//! fan-out directories for on-disk layouts.
+//!
+//! The leading segment is the version of the layout, currently [`ASCII_VERSION`]. It is checked
+//! when parsing so that a future layout cannot be misread as this one.
+//!
+//! Sharded paths insert `depth` directories of `width` hex characters taken from the start of
+//! the digest, and keep the whole digest as the last segment, so `1/b/fc/ca/fcca42…` holds the
+//! same id as `1/b/fcca42…`.
+//!
+//! # Examples
+//! ```
+//! use okid::{to_ascii_sharded, OkId, Sharding};
+//!
+//! let id = okid::okid!("bːfcca4276240cd3aa68d8fbb4917e8392c1166a3fcbf7c186b05e4599f38d391a");
+//! let path = to_ascii_sharded(id, Sharding::new(2, 2));
+//! assert_eq!(
+//!     path,
+//!     "1/b/fc/ca/fcca4276240cd3aa68d8fbb4917e8392c1166a3fcbf7c186b05e4599f38d391a"
+//! );
+//! assert_eq!(OkId::from_ascii(&path).unwrap(), id);
+//! assert_eq!(OkId::from_ascii(&okid::to_ascii(id)).unwrap(), id);
+//! ```
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::{parse::parse_okid, Error, OkId, SEPARATOR};
+
+/// The version segment at the start of every path-safe OkId.
+pub const ASCII_VERSION: &str = "1";
+
+/// Fan-out directories for sharded path-safe OkIds.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Sharding {
+    /// Hex characters per directory
+    pub width: usize,
+    /// Number of directories
+    pub depth: usize,
+}
+
+impl Sharding {
+    /// `depth` directories of `width` hex characters each.
+    pub fn new(width: usize, depth: usize) -> Self {
+        Sharding { width, depth }
+    }
+
+    /// The shard directories for a hex digest. Shards that would not fit in the digest are
+    /// left out.
+    fn shards(self, hex: &str) -> impl Iterator<Item = &str> {
+        let width = self.width;
+        (0..self.depth)
+            .take_while(move |i| width > 0 && (i + 1) * width <= hex.len())
+            .map(move |i| &hex[i * width..(i + 1) * width])
+    }
+}
+
+/// Create a sharded path-safe string from an OkId.
+///
+/// With the default [`Sharding`] this is the same as [`to_ascii`](crate::to_ascii).
+pub fn to_ascii_sharded(id: OkId, sharding: Sharding) -> String {
+    let hex = id.digest.to_string();
+    let mut out = format!("{ASCII_VERSION}/{}/", id.hash_type.char_code());
+    for shard in sharding.shards(&hex) {
+        out.push_str(shard);
+        out.push('/');
+    }
+    out.push_str(&hex);
+    out
+}
+
+/// Create a sharded relative path from an OkId, using the platform separator.
+pub fn to_ascii_path(id: OkId, sharding: Sharding) -> PathBuf {
+    to_ascii_sharded(id, sharding).split('/').collect()
+}
+
+/// Parse the segments of a path-safe OkId, with any sharding.
+fn from_segments<'a>(segments: impl IntoIterator<Item = &'a str>) -> Result<OkId, Error> {
+    let mut segments = segments.into_iter();
+    match segments.next() {
+        Some(ASCII_VERSION) => {}
+        Some(version) if !version.is_empty() && version.bytes().all(|b| b.is_ascii_digit()) => {
+            return Err(Error::UnsupportedVersion(version.to_string()))
+        }
+        _ => return Err(Error::InvalidFormat),
+    }
+    let code = segments.next().ok_or(Error::InvalidFormat)?;
+    let rest: Vec<&str> = segments.collect();
+    let (hex, shards) = rest.split_last().ok_or(Error::InvalidFormat)?;
+    // Each shard must continue the digest prefix, so stray directories are not ignored
+    let mut offset = 0;
+    for shard in shards {
+        if shard.is_empty() || hex.get(offset..offset + shard.len()) != Some(*shard) {
+            return Err(Error::InvalidFormat);
+        }
+        offset += shard.len();
+    }
+    if code.chars().count() != 1 {
+        return Err(Error::InvalidDigestType);
+    }
+    parse_okid(&format!("{code}{SEPARATOR}{hex}"))
+}
+
+impl OkId {
+    /// Parse a path-safe OkId made by [`to_ascii`](crate::to_ascii) or [`to_ascii_sharded`],
+    /// with any sharding.
+    ///
+    /// Fails with [`Error::UnsupportedVersion`] if the leading version is not
+    /// [`ASCII_VERSION`].
+    pub fn from_ascii(s: &str) -> Result<OkId, Error> {
+        from_segments(s.split('/'))
+    }
+
+    /// Parse a relative path made by [`to_ascii_path`], for walking a sharded directory tree.
+    ///
+    /// The path must start at the version directory, so strip the root of the tree first.
+    ///
+    /// ```
+    /// use okid::{to_ascii_path, OkId, Sharding};
+    ///
+    /// let id = okid::okid!("uː4d3881627191c1d4236405ac98409b01");
+    /// let root = std::path::Path::new("/var/blobs");
+    /// let path = root.join(to_ascii_path(id, Sharding::new(2, 1)));
+    /// assert_eq!(OkId::from_ascii_path(path.strip_prefix(root).unwrap()).unwrap(), id);
+    /// ```
+    pub fn from_ascii_path(path: &Path) -> Result<OkId, Error> {
+        let segments = path
+            .components()
+            .map(|component| match component {
+                Component::Normal(segment) => segment.to_str().ok_or(Error::InvalidFormat),
+                _ => Err(Error::InvalidFormat),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        from_segments(segments)
+    }
+}
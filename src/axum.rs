@@ -0,0 +1,151 @@
+//! Axum and Tower integration: an OkId path extractor and a layer that adds OkId ETags.
+//!
+//! # Examples
+//! ```
+//! use axum::{routing::get, Router};
+//! use okid::axum::{ETagLayer, OkIdPath};
+//!
+//! async fn file(OkIdPath(id): OkIdPath) -> String {
+//!     format!("contents of {id}")
+//! }
+//!
+//! let app: Router = Router::new()
+//!     .route("/files/{id}/{name}", get(file))
+//!     .layer(ETagLayer::new());
+//! ```
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::FromRequestParts,
+    http::{header, request::Parts, Method, Request, Response, StatusCode},
+    response::IntoResponse,
+    BoxError,
+};
+use headers::{ETag, HeaderMapExt};
+use http_body_util::BodyExt;
+use sha2::Digest as _;
+use tower::{Layer, Service};
+
+use crate::{http::IfNoneMatch, okid::from_path_segments, Error, OkId};
+
+/// Extracts the first OkId in the request path, percent-decoding each segment.
+///
+/// Rejects with `400 Bad Request` naming the parse error if no segment is an OkId.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OkIdPath(pub OkId);
+
+/// The rejection of [`OkIdPath`].
+#[derive(Debug)]
+pub struct OkIdRejection(pub Error);
+
+impl fmt::Display for OkIdRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid OkId in path: {}", self.0)
+    }
+}
+
+impl std::error::Error for OkIdRejection {}
+
+impl IntoResponse for OkIdRejection {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for OkIdPath {
+    type Rejection = OkIdRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        from_path_segments(parts.uri.path().split('/'))
+            .map(OkIdPath)
+            .map_err(OkIdRejection)
+    }
+}
+
+/// Adds a SHA-256 OkId `ETag` to successful `GET` responses and answers matching
+/// `If-None-Match` requests with `304 Not Modified`.
+///
+/// Response bodies without an `ETag` are buffered to hash them. Responses that already carry
+/// an OkId `ETag` are compared against it without buffering, and other `ETag`s are left
+/// alone.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ETagLayer;
+
+impl ETagLayer {
+    /// Create the layer.
+    pub fn new() -> Self {
+        ETagLayer
+    }
+}
+
+impl<S> Layer<S> for ETagLayer {
+    type Service = ETagService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ETagService { inner }
+    }
+}
+
+/// The service of [`ETagLayer`].
+#[derive(Clone, Debug)]
+pub struct ETagService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for ETagService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    ResBody: HttpBody<Data = Bytes> + Send + 'static,
+    ResBody::Error: Into<BoxError>,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // HEAD bodies are empty, so hashing them would give the wrong tag
+        let applies = request.method() == Method::GET;
+        let if_none_match = request.headers().typed_get::<IfNoneMatch>();
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await?;
+            if !applies || response.status() != StatusCode::OK {
+                return Ok(response.map(Body::new));
+            }
+            let (mut parts, body) = response.into_parts();
+            let (id, body) = match parts.headers.typed_get::<ETag>() {
+                Some(etag) => match OkId::try_from(&etag) {
+                    Ok(id) => (id, Body::new(body)),
+                    Err(_) => return Ok(Response::from_parts(parts, Body::new(body))),
+                },
+                None => {
+                    let bytes = match body.collect().await {
+                        Ok(collected) => collected.to_bytes(),
+                        Err(_) => return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response()),
+                    };
+                    let id: OkId = sha2::Sha256::new_with_prefix(&bytes).into();
+                    parts.headers.typed_insert(ETag::from(id));
+                    (id, Body::from(bytes))
+                }
+            };
+            if if_none_match.is_some_and(|if_none_match| if_none_match.matches(&id)) {
+                parts.status = StatusCode::NOT_MODIFIED;
+                parts.headers.remove(header::CONTENT_LENGTH);
+                return Ok(Response::from_parts(parts, Body::empty()));
+            }
+            Ok(Response::from_parts(parts, body))
+        })
+    }
+}
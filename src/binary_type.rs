@@ -37,6 +37,18 @@ pub(crate) enum BinaryType {
     Fingerprint = b'f',
     // PubKey
     PubKey = b'p',
+    // Ed25519 signature
+    Signature = b's',
+    // Ed25519 secret key
+    SecretKey = b'k',
+    // X25519 public key
+    X25519 = b'x',
+    // Compressed secp256k1 public key
+    Secp256k1 = b'e',
+    // Compressed P-256 (secp256r1) public key
+    P256 = b'r',
+    // Nix store path hash (SHA-256 compressed to 160 bits)
+    NixStore = b'n',
 }
 
 impl FromStr for BinaryType {
@@ -58,7 +70,13 @@ impl FromStr for BinaryType {
             #[cfg(feature = "uuid")]
             "uuid" => Ok(Self::Uuid),
             "fingerprint" => Ok(Self::Fingerprint),
-            "pub_key" => Ok(Self::PubKey),
+            "pub_key" | "pubkey" => Ok(Self::PubKey),
+            "signature" => Ok(Self::Signature),
+            "secret_key" => Ok(Self::SecretKey),
+            "x25519" => Ok(Self::X25519),
+            "secp256k1" => Ok(Self::Secp256k1),
+            "p256" => Ok(Self::P256),
+            "nix_store" => Ok(Self::NixStore),
             _ => Err(Error::InvalidDigestType),
         }
     }
@@ -81,6 +99,12 @@ impl From<char> for BinaryType {
             'i' => Self::Uuid,
             'f' => Self::Fingerprint,
             'p' => Self::PubKey,
+            's' => Self::Signature,
+            'k' => Self::SecretKey,
+            'x' => Self::X25519,
+            'e' => Self::Secp256k1,
+            'r' => Self::P256,
+            'n' => Self::NixStore,
             _ => Self::Unknown,
         }
     }
@@ -111,6 +135,12 @@ impl Display for BinaryType {
             BinaryType::Unknown => write!(f, "unknown"),
             BinaryType::Fingerprint => write!(f, "fingerprint"),
             BinaryType::PubKey => write!(f, "pubkey"),
+            BinaryType::Signature => write!(f, "signature"),
+            BinaryType::SecretKey => write!(f, "secret_key"),
+            BinaryType::X25519 => write!(f, "x25519"),
+            BinaryType::Secp256k1 => write!(f, "secp256k1"),
+            BinaryType::P256 => write!(f, "p256"),
+            BinaryType::NixStore => write!(f, "nix_store"),
         }
     }
 }
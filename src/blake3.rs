@@ -43,6 +43,49 @@ impl From<blake3::Hasher> for OkId {
 
 impl IntoOkId for blake3::Hasher {}
 
+#[cfg(feature = "iroh")]
+impl From<iroh_blobs::Hash> for OkId {
+    fn from(value: iroh_blobs::Hash) -> Self {
+        Self {
+            hash_type: BinaryType::Blake3,
+            digest: Digest::Blake3(Blake3(*value.as_bytes())),
+        }
+    }
+}
+
+#[cfg(feature = "iroh")]
+impl IntoOkId for iroh_blobs::Hash {}
+
+#[cfg(feature = "iroh")]
+impl TryFrom<OkId> for iroh_blobs::Hash {
+    type Error = crate::Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        match value.digest {
+            Digest::Blake3(blake3) => Ok(iroh_blobs::Hash::from_bytes(blake3.0)),
+            _ => Err(crate::Error::InvalidType),
+        }
+    }
+}
+
+/// The format is not part of the OkId; use [`iroh_blobs::HashAndFormat::hash`] if it matters.
+#[cfg(feature = "iroh")]
+impl From<iroh_blobs::HashAndFormat> for OkId {
+    fn from(value: iroh_blobs::HashAndFormat) -> Self {
+        value.hash.into()
+    }
+}
+
+/// Converts to a [`iroh_blobs::BlobFormat::Raw`] blob.
+#[cfg(feature = "iroh")]
+impl TryFrom<OkId> for iroh_blobs::HashAndFormat {
+    type Error = crate::Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        Ok(iroh_blobs::HashAndFormat::raw(value.try_into()?))
+    }
+}
+
 impl Display for Blake3 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let data = self.0;
@@ -57,6 +100,9 @@ impl std::str::FromStr for Blake3 {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let buf = hex::decode(s)?;
+        if buf.len() != 32 {
+            return Err(super::Error::InvalidLength);
+        }
         let mut hash: [u8; 32] = [0; 32];
         hash.copy_from_slice(&buf);
         Ok(Blake3(hash))
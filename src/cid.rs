@@ -0,0 +1,127 @@
+//! IPFS CIDs, multihashes and multibase strings.
+//!
+//! Hash kinds use their multihash codes. Other kinds are embedded whole with the identity
+//! multihash, so they survive a round trip too; signatures are the exception, as they are too
+//! large for a [`Cid`] and are identified by the SHA-256 of their binary encoding instead.
+//!
+//! # Examples
+//! ```
+//! use cid::{multibase::Base, Cid};
+//! use okid::OkId;
+//!
+//! let id: OkId = "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+//!     .parse()
+//!     .unwrap();
+//! let cid = Cid::from(id);
+//! assert_eq!(
+//!     cid.to_string(),
+//!     "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+//! );
+//! assert_eq!(OkId::try_from(&cid).unwrap(), id);
+//!
+//! let dag_cbor = id.to_cid_string(okid::cid::DAG_CBOR, Base::Base58Btc).unwrap();
+//! assert!(dag_cbor.starts_with('z'));
+//! assert_eq!(OkId::from_cid_str(&dag_cbor).unwrap(), id);
+//! ```
+
+use cid::{multibase::Base, multihash::Multihash, Cid};
+use jetstream_wireformat::WireFormat;
+use sha2::Digest as _;
+
+use crate::{
+    multicodec::{hash_codec, hash_kind, hash_name},
+    Error, OkId,
+};
+
+/// Multicodec code for raw bytes.
+pub const RAW: u64 = 0x55;
+/// Multicodec code for MerkleDAG protobuf.
+pub const DAG_PB: u64 = 0x70;
+/// Multicodec code for MerkleDAG CBOR.
+pub const DAG_CBOR: u64 = 0x71;
+/// Multicodec code for MerkleDAG JSON.
+pub const DAG_JSON: u64 = 0x0129;
+
+const IDENTITY: u64 = 0x00;
+const SHA2_256: u64 = 0x12;
+const MAX_DIGEST_LEN: usize = 64;
+
+impl OkId {
+    /// Encode as a binary multihash.
+    pub fn to_multihash(&self) -> Vec<u8> {
+        multihash(self).to_bytes()
+    }
+
+    /// Decode a binary multihash.
+    pub fn from_multihash(bytes: &[u8]) -> Result<OkId, Error> {
+        let multihash =
+            Multihash::<MAX_DIGEST_LEN>::from_bytes(bytes).map_err(|_| Error::InvalidFormat)?;
+        from_multihash(&multihash)
+    }
+
+    /// A CIDv1 with the given content codec, e.g. [`RAW`] or [`DAG_CBOR`].
+    pub fn to_cid(&self, codec: u64) -> Cid {
+        Cid::new_v1(codec, multihash(self))
+    }
+
+    /// A CIDv1 with the given content codec, as a multibase string.
+    pub fn to_cid_string(&self, codec: u64, base: Base) -> Result<String, Error> {
+        self.to_cid(codec)
+            .to_string_of_base(base)
+            .map_err(|_| Error::InvalidFormat)
+    }
+
+    /// Parse a CID in any multibase, or a CIDv0 `Qm…` string.
+    pub fn from_cid_str(s: &str) -> Result<OkId, Error> {
+        let cid = Cid::try_from(s).map_err(|_| Error::InvalidFormat)?;
+        OkId::try_from(&cid)
+    }
+}
+
+/// A raw CIDv1; use [`OkId::to_cid`] to pick another codec.
+impl From<OkId> for Cid {
+    fn from(value: OkId) -> Self {
+        value.to_cid(RAW)
+    }
+}
+
+impl TryFrom<&Cid> for OkId {
+    type Error = Error;
+
+    /// Fails with [`Error::UnsupportedAlgorithm`] for hash functions without an OkId kind.
+    fn try_from(cid: &Cid) -> Result<Self, Self::Error> {
+        from_multihash(cid.hash())
+    }
+}
+
+fn multihash(id: &OkId) -> Multihash<MAX_DIGEST_LEN> {
+    let (code, digest) = match hash_codec(id.hash_type) {
+        Some(code) => (code, id.digest_bytes()),
+        None => {
+            let mut encoded = Vec::with_capacity(id.encoded_size());
+            id.encode(&mut encoded)
+                .expect("encoding into a Vec cannot fail");
+            if encoded.len() <= MAX_DIGEST_LEN {
+                (IDENTITY, encoded)
+            } else {
+                (SHA2_256, sha2::Sha256::digest(&encoded).to_vec())
+            }
+        }
+    };
+    Multihash::wrap(code, &digest).expect("digest fits in 64 bytes")
+}
+
+fn from_multihash(multihash: &Multihash<MAX_DIGEST_LEN>) -> Result<OkId, Error> {
+    if multihash.code() == IDENTITY {
+        let mut reader = multihash.digest();
+        let id = OkId::decode(&mut reader).map_err(|_| Error::InvalidFormat)?;
+        if !reader.is_empty() {
+            return Err(Error::InvalidLength);
+        }
+        return Ok(id);
+    }
+    match hash_kind(multihash.code()) {
+        Some(kind) => OkId::from_digest_bytes(kind, multihash.digest()),
+        None => Err(Error::UnsupportedAlgorithm(hash_name(multihash.code()))),
+    }
+}
@@ -0,0 +1,200 @@
+//! Salted commit/reveal scheme producing `OkId` commitments.
+//!
+//! A commitment hides `data` behind a random 32 byte salt (the [`Opening`]) and binds
+//! to it through SHA-256 or BLAKE3. Both hashes are domain separated so a commitment
+//! can never be confused with a plain content hash of the same bytes.
+//!
+//! # Examples
+//! ```
+//! use okid::commitment::{self, CommitmentAlgorithm};
+//!
+//! let (commitment, opening) = okid::OkId::commit(CommitmentAlgorithm::default(), b"my bid: 42").unwrap();
+//! // publish `commitment`, keep `opening` until the reveal
+//! assert!(commitment::verify(&commitment, b"my bid: 42", &opening));
+//! assert!(!commitment::verify(&commitment, b"my bid: 43", &opening));
+//! ```
+
+use std::{fmt::Display, str::FromStr};
+
+use jetstream_wireformat::WireFormat;
+use serde::{de::Visitor, Deserialize, Serialize};
+#[cfg(wasm_bindgen)]
+use wasm_bindgen::prelude::*;
+
+use crate::{BinaryType, Digest, Error, OkId};
+
+/// Size of the random salt in bytes.
+pub const OPENING_LEN: usize = 32;
+
+#[cfg(feature = "sha2")]
+const SHA256_DOMAIN: &[u8] = b"okid commitment v1 sha256\0";
+#[cfg(feature = "blake3")]
+const BLAKE3_CONTEXT: &str = "okid commitment v1 blake3";
+
+/// Hash functions supported by the commitment helpers.
+#[cfg_attr(wasm_bindgen, wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CommitmentAlgorithm {
+    #[cfg(feature = "sha2")]
+    #[cfg_attr(not(feature = "blake3"), default)]
+    /// SHA-256 over a domain tag, the salt and the data
+    Sha256,
+    #[cfg(feature = "blake3")]
+    #[default]
+    /// BLAKE3 in key derivation mode, keyed by a domain context
+    Blake3,
+}
+
+/// The secret half of a commitment, revealed together with the data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Opening([u8; OPENING_LEN]);
+
+impl Opening {
+    /// Generate a new random opening.
+    pub fn random() -> Result<Self, Error> {
+        let mut salt = [0u8; OPENING_LEN];
+        getrandom::fill(&mut salt)?;
+        Ok(Opening(salt))
+    }
+
+    /// Create an opening from raw salt bytes.
+    pub const fn from_bytes(salt: [u8; OPENING_LEN]) -> Self {
+        Opening(salt)
+    }
+
+    /// The raw salt bytes.
+    pub const fn as_bytes(&self) -> &[u8; OPENING_LEN] {
+        &self.0
+    }
+}
+
+impl Display for Opening {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl FromStr for Opening {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let buf = hex::decode(s)?;
+        if buf.len() != OPENING_LEN {
+            return Err(Error::InvalidLength);
+        }
+        let mut salt = [0u8; OPENING_LEN];
+        salt.copy_from_slice(&buf);
+        Ok(Opening(salt))
+    }
+}
+
+impl WireFormat for Opening {
+    fn byte_size(&self) -> u32 {
+        OPENING_LEN as u32
+    }
+
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.0)
+    }
+
+    fn decode<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut salt = [0u8; OPENING_LEN];
+        reader.read_exact(&mut salt)?;
+        Ok(Opening(salt))
+    }
+}
+
+impl Serialize for Opening {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+struct OpeningVisitor;
+
+impl Visitor<'_> for OpeningVisitor {
+    type Value = Opening;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a hex encoded 32 byte commitment opening")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse()
+            .map_err(|e| E::custom(format!("Invalid opening: {}", e)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Opening {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(OpeningVisitor)
+    }
+}
+
+impl OkId {
+    /// Commit to `data` with a fresh random opening.
+    ///
+    /// Returns the commitment, which is safe to publish, and the opening, which must be kept
+    /// until the data is revealed.
+    pub fn commit(algorithm: CommitmentAlgorithm, data: &[u8]) -> Result<(OkId, Opening), Error> {
+        let opening = Opening::random()?;
+        Ok((
+            OkId::commit_with_opening(algorithm, data, &opening),
+            opening,
+        ))
+    }
+
+    /// Commit to `data` using a caller supplied opening.
+    pub fn commit_with_opening(
+        algorithm: CommitmentAlgorithm,
+        data: &[u8],
+        opening: &Opening,
+    ) -> OkId {
+        match algorithm {
+            #[cfg(feature = "sha2")]
+            CommitmentAlgorithm::Sha256 => {
+                use sha2::Digest as _;
+                let mut hasher = sha2::Sha256::new();
+                hasher.update(SHA256_DOMAIN);
+                hasher.update(opening.0);
+                hasher.update(data);
+                let mut buf = [0u8; 32];
+                buf.copy_from_slice(&hasher.finalize());
+                OkId {
+                    hash_type: BinaryType::Sha256,
+                    digest: Digest::Sha256(crate::sha2::Sha256(buf)),
+                }
+            }
+            #[cfg(feature = "blake3")]
+            CommitmentAlgorithm::Blake3 => {
+                let mut hasher = blake3::Hasher::new_derive_key(BLAKE3_CONTEXT);
+                hasher.update(&opening.0);
+                hasher.update(data);
+                hasher.into()
+            }
+        }
+    }
+
+    /// Check that this commitment was made to `data` with `opening`.
+    pub fn verify_commitment(&self, data: &[u8], opening: &Opening) -> bool {
+        let algorithm = match self.hash_type {
+            #[cfg(feature = "sha2")]
+            BinaryType::Sha256 => CommitmentAlgorithm::Sha256,
+            #[cfg(feature = "blake3")]
+            BinaryType::Blake3 => CommitmentAlgorithm::Blake3,
+            _ => return false,
+        };
+        OkId::commit_with_opening(algorithm, data, opening) == *self
+    }
+}
+
+/// Commit to `data` with the default algorithm and a fresh random opening.
+pub fn commit(data: impl AsRef<[u8]>) -> Result<(OkId, Opening), Error> {
+    OkId::commit(CommitmentAlgorithm::default(), data.as_ref())
+}
+
+/// Check that `commitment` was made to `data` with `opening`.
+pub fn verify(commitment: &OkId, data: impl AsRef<[u8]>, opening: &Opening) -> bool {
+    commitment.verify_commitment(data.as_ref(), opening)
+}
@@ -0,0 +1,52 @@
+//! W3C `did:key` identifiers and multibase multikeys for public key OkIds.
+//!
+//! # Examples
+//! ```
+//! use okid::OkId;
+//!
+//! let did = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+//! let key = OkId::from_did_key(did).unwrap();
+//! assert_eq!(key.to_did_key().unwrap(), did);
+//! ```
+
+use crate::{
+    multicodec::{key_codec, key_kind, read_varint, write_varint},
+    Error, OkId,
+};
+
+const DID_KEY_PREFIX: &str = "did:key:";
+const BASE58BTC: char = 'z';
+
+impl OkId {
+    /// Encode this public key as a multibase (base58btc) multicodec key, e.g. `z6Mk…`.
+    pub fn to_multikey(&self) -> Result<String, Error> {
+        let code = key_codec(self.hash_type).ok_or(Error::InvalidType)?;
+        let mut buf = Vec::with_capacity(self.encoded_size() + 2);
+        write_varint(code, &mut buf);
+        buf.extend_from_slice(&self.digest_bytes());
+        Ok(format!("{}{}", BASE58BTC, bs58::encode(buf).into_string()))
+    }
+
+    /// Decode a multibase (base58btc) multicodec key such as `z6Mk…`.
+    pub fn from_multikey(s: &str) -> Result<OkId, Error> {
+        let encoded = s.strip_prefix(BASE58BTC).ok_or(Error::InvalidFormat)?;
+        let bytes = bs58::decode(encoded)
+            .into_vec()
+            .map_err(|_| Error::InvalidFormat)?;
+        let (code, key) = read_varint(&bytes).ok_or(Error::InvalidFormat)?;
+        let hash_type = key_kind(code).ok_or(Error::InvalidType)?;
+        OkId::from_digest_bytes(hash_type, key)
+    }
+
+    /// Encode this public key as a `did:key` identifier.
+    pub fn to_did_key(&self) -> Result<String, Error> {
+        Ok(format!("{}{}", DID_KEY_PREFIX, self.to_multikey()?))
+    }
+
+    /// Parse a `did:key` identifier, ignoring any `#…` fragment.
+    pub fn from_did_key(s: &str) -> Result<OkId, Error> {
+        let multikey = s.strip_prefix(DID_KEY_PREFIX).ok_or(Error::InvalidFormat)?;
+        let multikey = multikey.split('#').next().unwrap_or_default();
+        OkId::from_multikey(multikey)
+    }
+}
@@ -29,6 +29,12 @@ pub(crate) enum Digest {
     Uuid(crate::uuid::Uuid),
     Fingerprint(crate::fingerprint::Fingerprint),
     PubKey(crate::pub_key::PubKey),
+    Signature(crate::signature::Signature),
+    SecretKey(crate::secret_key::SecretKey),
+    X25519(crate::x25519::X25519),
+    Secp256k1(crate::secp256k1::Secp256k1),
+    P256(crate::p256::P256),
+    NixStore(crate::nix_store::NixStore),
 }
 
 impl Display for Digest {
@@ -48,6 +54,12 @@ impl Display for Digest {
             Digest::Uuid(uuid) => write!(f, "{}", uuid),
             Digest::Fingerprint(fingerprint) => write!(f, "{}", fingerprint),
             Digest::PubKey(pub_key) => write!(f, "{}", pub_key),
+            Digest::Signature(signature) => write!(f, "{}", signature),
+            Digest::SecretKey(secret_key) => write!(f, "{}", secret_key),
+            Digest::X25519(x25519) => write!(f, "{}", x25519),
+            Digest::Secp256k1(secp256k1) => write!(f, "{}", secp256k1),
+            Digest::P256(p256) => write!(f, "{}", p256),
+            Digest::NixStore(nix_store) => write!(f, "{}", nix_store),
         }
     }
 }
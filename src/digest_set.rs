@@ -0,0 +1,460 @@
+//! Digest sets for supply-chain metadata: in-toto and SLSA `digest` maps, SPDX `checksums`
+//! and CycloneDX `hashes`.
+//!
+//! A [`DigestSet`] holds one OkId per hash kind for a single artifact. Entries for algorithms
+//! without an OkId kind, such as `sha512`, are kept as hex so documents round-trip, but are
+//! not verified.
+//!
+//! # Examples
+//! ```
+//! use okid::digest_set::{Algorithm, DigestSet};
+//!
+//! let artifact = b"hello world";
+//! let digests = DigestSet::from_reader(&artifact[..], &[Algorithm::Sha256]).unwrap();
+//! assert_eq!(
+//!     digests.get("sha256").unwrap().to_string(),
+//!     "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+//! );
+//! digests.verify(&artifact[..]).unwrap();
+//! assert!(digests.verify(&b"hello world!"[..]).is_err());
+//! ```
+//!
+//! With `#[serde(with = …)]` the same set serializes in each document format:
+//! ```
+//! use okid::digest_set::DigestSet;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct SpdxFile {
+//!     #[serde(with = "okid::digest_set::spdx")]
+//!     checksums: DigestSet,
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct CycloneDxComponent {
+//!     #[serde(with = "okid::digest_set::cyclonedx")]
+//!     hashes: DigestSet,
+//! }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Read},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::Digest as _;
+
+use crate::{BinaryType, Error, OkId};
+
+/// `[in-toto, SPDX, CycloneDX]` names of well known algorithms. The in-toto name is the key
+/// used internally; algorithms CycloneDX does not define use the SPDX name there.
+const NAMES: &[[&str; 3]] = &[
+    ["blake3", "BLAKE3", "BLAKE3"],
+    ["md5", "MD5", "MD5"],
+    ["sha1", "SHA1", "SHA-1"],
+    ["sha224", "SHA224", "SHA224"],
+    ["sha256", "SHA256", "SHA-256"],
+    ["sha384", "SHA384", "SHA-384"],
+    ["sha512", "SHA512", "SHA-512"],
+    ["sha3_256", "SHA3-256", "SHA3-256"],
+    ["sha3_384", "SHA3-384", "SHA3-384"],
+    ["sha3_512", "SHA3-512", "SHA3-512"],
+];
+const SPDX: usize = 1;
+const CYCLONEDX: usize = 2;
+
+/// A hash algorithm with an OkId kind.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Algorithm {
+    /// `sha1`
+    #[cfg(feature = "sha1")]
+    Sha1,
+    /// `sha256`
+    Sha256,
+    /// `sha3_512`
+    #[cfg(feature = "sha3")]
+    Sha3_512,
+    /// `blake3`
+    #[cfg(feature = "blake3")]
+    Blake3,
+}
+
+impl Algorithm {
+    /// The in-toto digest set name.
+    pub fn name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "sha1")]
+            Algorithm::Sha1 => "sha1",
+            Algorithm::Sha256 => "sha256",
+            #[cfg(feature = "sha3")]
+            Algorithm::Sha3_512 => "sha3_512",
+            #[cfg(feature = "blake3")]
+            Algorithm::Blake3 => "blake3",
+        }
+    }
+
+    fn of(id: &OkId) -> Option<Self> {
+        match id.hash_type {
+            #[allow(deprecated)]
+            #[cfg(feature = "sha1")]
+            BinaryType::Sha1 => Some(Algorithm::Sha1),
+            BinaryType::Sha256 => Some(Algorithm::Sha256),
+            #[cfg(feature = "sha3")]
+            BinaryType::Sha3_512 => Some(Algorithm::Sha3_512),
+            #[cfg(feature = "blake3")]
+            BinaryType::Blake3 => Some(Algorithm::Blake3),
+            _ => None,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            #[cfg(feature = "sha1")]
+            "sha1" => Some(Algorithm::Sha1),
+            "sha256" => Some(Algorithm::Sha256),
+            #[cfg(feature = "sha3")]
+            "sha3_512" => Some(Algorithm::Sha3_512),
+            #[cfg(feature = "blake3")]
+            "blake3" => Some(Algorithm::Blake3),
+            _ => None,
+        }
+    }
+
+    fn hash_type(self) -> BinaryType {
+        match self {
+            #[allow(deprecated)]
+            #[cfg(feature = "sha1")]
+            Algorithm::Sha1 => BinaryType::Sha1,
+            Algorithm::Sha256 => BinaryType::Sha256,
+            #[cfg(feature = "sha3")]
+            Algorithm::Sha3_512 => BinaryType::Sha3_512,
+            #[cfg(feature = "blake3")]
+            Algorithm::Blake3 => BinaryType::Blake3,
+        }
+    }
+
+    fn hasher(self) -> Hasher {
+        match self {
+            #[cfg(feature = "sha1")]
+            Algorithm::Sha1 => Hasher::Sha1(sha1::Sha1::new()),
+            Algorithm::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+            #[cfg(feature = "sha3")]
+            Algorithm::Sha3_512 => Hasher::Sha3_512(sha3::Sha3_512::new()),
+            #[cfg(feature = "blake3")]
+            Algorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+}
+
+enum Hasher {
+    #[cfg(feature = "sha1")]
+    Sha1(sha1::Sha1),
+    Sha256(sha2::Sha256),
+    #[cfg(feature = "sha3")]
+    Sha3_512(sha3::Sha3_512),
+    #[cfg(feature = "blake3")]
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            #[cfg(feature = "sha1")]
+            Hasher::Sha1(hasher) => hasher.update(data),
+            Hasher::Sha256(hasher) => hasher.update(data),
+            #[cfg(feature = "sha3")]
+            Hasher::Sha3_512(hasher) => hasher.update(data),
+            #[cfg(feature = "blake3")]
+            Hasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    fn finalize(self) -> OkId {
+        match self {
+            #[cfg(feature = "sha1")]
+            Hasher::Sha1(hasher) => hasher.into(),
+            Hasher::Sha256(hasher) => hasher.into(),
+            #[cfg(feature = "sha3")]
+            Hasher::Sha3_512(hasher) => hasher.into(),
+            #[cfg(feature = "blake3")]
+            Hasher::Blake3(hasher) => (*hasher).into(),
+        }
+    }
+}
+
+/// The digests of one artifact, keyed by in-toto algorithm name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DigestSet {
+    ids: BTreeMap<Algorithm, OkId>,
+    other: BTreeMap<String, String>,
+}
+
+impl DigestSet {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        DigestSet::default()
+    }
+
+    /// Hash an artifact with each of `algorithms` in a single pass.
+    pub fn from_reader(mut reader: impl Read, algorithms: &[Algorithm]) -> io::Result<Self> {
+        let mut hashers = algorithms
+            .iter()
+            .map(|algorithm| (*algorithm, algorithm.hasher()))
+            .collect::<Vec<_>>();
+        let mut buf = [0u8; 8192];
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            for (_, hasher) in hashers.iter_mut() {
+                hasher.update(&buf[..n]);
+            }
+        }
+        Ok(DigestSet {
+            ids: hashers
+                .into_iter()
+                .map(|(algorithm, hasher)| (algorithm, hasher.finalize()))
+                .collect(),
+            other: BTreeMap::new(),
+        })
+    }
+
+    /// Add `id`, replacing any entry of the same kind.
+    ///
+    /// Fails with [`Error::InvalidType`] for kinds that are not content hashes.
+    pub fn insert(&mut self, id: OkId) -> Result<&mut Self, Error> {
+        let algorithm = Algorithm::of(&id).ok_or(Error::InvalidType)?;
+        self.ids.insert(algorithm, id);
+        Ok(self)
+    }
+
+    /// Add a hex digest by in-toto algorithm name, e.g. `sha512`.
+    ///
+    /// Names with an OkId kind are parsed into one; other names are kept as lowercase hex.
+    pub fn insert_hex(&mut self, name: &str, hex: &str) -> Result<&mut Self, Error> {
+        let bytes = hex::decode(hex)?;
+        match Algorithm::from_name(name) {
+            Some(algorithm) => {
+                let id = OkId::from_digest_bytes(algorithm.hash_type(), &bytes)?;
+                self.ids.insert(algorithm, id);
+            }
+            None => {
+                self.other.insert(name.to_string(), hex::encode(bytes));
+            }
+        }
+        Ok(self)
+    }
+
+    /// The OkId for an in-toto algorithm name.
+    pub fn get(&self, name: &str) -> Option<OkId> {
+        self.ids.get(&Algorithm::from_name(name)?).copied()
+    }
+
+    /// All OkIds in the set.
+    pub fn ids(&self) -> impl Iterator<Item = OkId> + '_ {
+        self.ids.values().copied()
+    }
+
+    /// Entries without an OkId kind, as in-toto name and hex digest.
+    pub fn other(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.other
+            .iter()
+            .map(|(name, hex)| (name.as_str(), hex.as_str()))
+    }
+
+    /// Whether the set has no entries at all.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty() && self.other.is_empty()
+    }
+
+    /// Check an artifact against every entry with an OkId kind, reading it once.
+    ///
+    /// A mismatch is an [`io::ErrorKind::InvalidData`] error wrapping
+    /// [`Error::DigestMismatch`] for the first entry that differs. A set with no entry that
+    /// can be checked fails with [`io::ErrorKind::InvalidInput`] wrapping
+    /// [`Error::UnsupportedAlgorithm`].
+    pub fn verify(&self, reader: impl Read) -> io::Result<()> {
+        if self.ids.is_empty() {
+            let names = self.other.keys().cloned().collect::<Vec<_>>().join(", ");
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                Error::UnsupportedAlgorithm(names),
+            ));
+        }
+        let algorithms = self.ids.keys().copied().collect::<Vec<_>>();
+        let actual = DigestSet::from_reader(reader, &algorithms)?;
+        for (algorithm, expected) in &self.ids {
+            let actual = actual.ids[algorithm];
+            if actual != *expected {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    Error::DigestMismatch {
+                        expected: Box::new(*expected),
+                        actual: Box::new(actual),
+                    },
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Entries as in-toto name and lowercase hex, sorted by name.
+    fn entries(&self) -> BTreeMap<&str, String> {
+        self.ids
+            .iter()
+            .map(|(algorithm, id)| (algorithm.name(), hex::encode(id.digest_bytes())))
+            .chain(
+                self.other
+                    .iter()
+                    .map(|(name, hex)| (name.as_str(), hex.clone())),
+            )
+            .collect()
+    }
+}
+
+impl FromIterator<OkId> for DigestSet {
+    /// Collect hash OkIds, skipping kinds that are not content hashes.
+    fn from_iter<I: IntoIterator<Item = OkId>>(iter: I) -> Self {
+        let mut set = DigestSet::new();
+        for id in iter {
+            let _ = set.insert(id);
+        }
+        set
+    }
+}
+
+/// Serializes as an in-toto digest set, e.g. `{"sha256": "…"}`.
+impl Serialize for DigestSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.entries().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DigestSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = BTreeMap::<String, String>::deserialize(deserializer)?;
+        let mut set = DigestSet::new();
+        for (name, hex) in entries {
+            set.insert_hex(&name, &hex)
+                .map_err(|e| serde::de::Error::custom(format!("{}: {}", name, e)))?;
+        }
+        Ok(set)
+    }
+}
+
+/// An in-toto v1 ResourceDescriptor, as used for SLSA provenance subjects and dependencies.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceDescriptor {
+    /// Machine-readable identifier, e.g. a file name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// URI identifying the resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    /// Digests of the resource
+    #[serde(default, skip_serializing_if = "DigestSet::is_empty")]
+    pub digest: DigestSet,
+    /// Where the resource can be downloaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_location: Option<String>,
+    /// Media type of the resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+}
+
+/// The in-toto name for a name in `column`, or the name itself if it is not well known.
+fn to_intoto(name: &str, column: usize) -> String {
+    NAMES
+        .iter()
+        .find(|names| names[column] == name)
+        .map_or(name, |names| names[0])
+        .to_string()
+}
+
+/// The name in `column` for an in-toto name, or the name itself if it is not well known.
+fn from_intoto(name: &str, column: usize) -> &str {
+    NAMES
+        .iter()
+        .find(|names| names[0] == name)
+        .map_or(name, |names| names[column])
+}
+
+/// `#[serde(with = "okid::digest_set::spdx")]` for SPDX 2 `checksums` arrays.
+pub mod spdx {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::DigestSet;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Checksum {
+        algorithm: String,
+        checksum_value: String,
+    }
+
+    /// Serialize as `[{"algorithm": "SHA256", "checksumValue": "…"}]`.
+    pub fn serialize<S: Serializer>(set: &DigestSet, serializer: S) -> Result<S::Ok, S::Error> {
+        set.entries()
+            .into_iter()
+            .map(|(name, checksum_value)| Checksum {
+                algorithm: super::from_intoto(name, super::SPDX).to_string(),
+                checksum_value,
+            })
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    /// Deserialize from `[{"algorithm": "SHA256", "checksumValue": "…"}]`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DigestSet, D::Error> {
+        let mut set = DigestSet::new();
+        for checksum in Vec::<Checksum>::deserialize(deserializer)? {
+            let name = super::to_intoto(&checksum.algorithm, super::SPDX);
+            set.insert_hex(&name, &checksum.checksum_value)
+                .map_err(|e| serde::de::Error::custom(format!("{}: {}", checksum.algorithm, e)))?;
+        }
+        Ok(set)
+    }
+}
+
+/// `#[serde(with = "okid::digest_set::cyclonedx")]` for CycloneDX `hashes` arrays.
+pub mod cyclonedx {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::DigestSet;
+
+    #[derive(Serialize, Deserialize)]
+    struct Hash {
+        alg: String,
+        content: String,
+    }
+
+    /// Serialize as `[{"alg": "SHA-256", "content": "…"}]`.
+    pub fn serialize<S: Serializer>(set: &DigestSet, serializer: S) -> Result<S::Ok, S::Error> {
+        set.entries()
+            .into_iter()
+            .map(|(name, content)| Hash {
+                alg: super::from_intoto(name, super::CYCLONEDX).to_string(),
+                content,
+            })
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    /// Deserialize from `[{"alg": "SHA-256", "content": "…"}]`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DigestSet, D::Error> {
+        let mut set = DigestSet::new();
+        for hash in Vec::<Hash>::deserialize(deserializer)? {
+            let name = super::to_intoto(&hash.alg, super::CYCLONEDX);
+            set.insert_hex(&name, &hash.content)
+                .map_err(|e| serde::de::Error::custom(format!("{}: {}", hash.alg, e)))?;
+        }
+        Ok(set)
+    }
+}
@@ -2,6 +2,8 @@ use std::fmt::{self, Display};
 
 use ed25519_dalek::ed25519;
 
+use crate::OkId;
+
 #[derive(Debug, Clone)]
 /// Errors that can occur when parsing an OkId
 pub enum Error {
@@ -19,6 +21,21 @@ pub enum Error {
     InvalidType,
     /// No okid found
     NotFound,
+    /// The system random number generator failed
+    Random(getrandom::Error),
+    /// The algorithm is recognised but has no OkId kind
+    UnsupportedAlgorithm(String),
+    /// No key has the id of a sealed secret
+    UnknownKey(u32),
+    /// The version of a path-safe OkId is not supported
+    UnsupportedVersion(String),
+    /// Content did not hash to the expected OkId
+    DigestMismatch {
+        /// The OkId the content should have had
+        expected: Box<OkId>,
+        /// The OkId the content actually had
+        actual: Box<OkId>,
+    },
 }
 
 impl std::error::Error for Error {}
@@ -33,6 +50,13 @@ impl Display for Error {
             Error::InvalidSignature(e) => write!(f, "Invalid signature: {}", e),
             Error::InvalidType => write!(f, "Invalid type"),
             Error::NotFound => write!(f, "No okid found"),
+            Error::Random(e) => write!(f, "Random error: {}", e),
+            Error::UnsupportedAlgorithm(e) => write!(f, "Unsupported algorithm: {}", e),
+            Error::UnknownKey(id) => write!(f, "Unknown key id: {}", id),
+            Error::UnsupportedVersion(v) => write!(f, "Unsupported version: {}", v),
+            Error::DigestMismatch { expected, actual } => {
+                write!(f, "Digest mismatch: expected {}, got {}", expected, actual)
+            }
         }
     }
 }
@@ -50,6 +74,12 @@ impl From<ed25519::signature::Error> for Error {
     }
 }
 
+impl From<getrandom::Error> for Error {
+    fn from(e: getrandom::Error) -> Self {
+        Error::Random(e)
+    }
+}
+
 impl From<hex::FromHexError> for Error {
     fn from(e: hex::FromHexError) -> Self {
         Error::Hex(e)
@@ -40,6 +40,9 @@ impl FromStr for Fingerprint {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let buf = hex::decode(s)?;
+        if buf.len() != 8 {
+            return Err(super::Error::InvalidLength);
+        }
         let mut hash: [u8; 8] = [0; 8];
         hash.copy_from_slice(&buf);
         Ok(Fingerprint(U64::new(
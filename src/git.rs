@@ -0,0 +1,252 @@
+//! Git object ids in the SHA-1 and SHA-256 object formats.
+//!
+//! # Examples
+//! ```
+//! use okid::git::{blob_id, ObjectFormat};
+//!
+//! let id = blob_id(ObjectFormat::Sha1, b"hello world\n");
+//! assert_eq!(id.to_git_oid().unwrap(), "3b18e512dba79e4c8300dd08aeb37f8e728b8dad");
+//! assert_eq!(okid::git::parse_oid("3b18e512dba79e4c8300dd08aeb37f8e728b8dad").unwrap(), id);
+//! ```
+
+use std::{fmt, fs, io, path::Path};
+
+use sha1::Digest as _;
+
+use crate::{BinaryType, Error, OkId};
+
+/// The hash function of a repository's objects.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ObjectFormat {
+    /// `extensions.objectFormat = sha1`, the default
+    #[default]
+    Sha1,
+    /// `extensions.objectFormat = sha256`
+    Sha256,
+}
+
+impl ObjectFormat {
+    /// The object format an OkId belongs to.
+    pub fn of(id: &OkId) -> Result<Self, Error> {
+        match id.hash_type {
+            #[allow(deprecated)]
+            BinaryType::Sha1 => Ok(ObjectFormat::Sha1),
+            BinaryType::Sha256 => Ok(ObjectFormat::Sha256),
+            _ => Err(Error::InvalidType),
+        }
+    }
+
+    fn hash(self, data: &[u8]) -> OkId {
+        match self {
+            ObjectFormat::Sha1 => sha1::Sha1::new_with_prefix(data).into(),
+            ObjectFormat::Sha256 => sha2::Sha256::new_with_prefix(data).into(),
+        }
+    }
+}
+
+/// The type of a git object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ObjectType {
+    /// File contents
+    Blob,
+    /// Directory listing
+    Tree,
+    /// Commit
+    Commit,
+    /// Annotated tag
+    Tag,
+}
+
+impl fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ObjectType::Blob => "blob",
+            ObjectType::Tree => "tree",
+            ObjectType::Commit => "commit",
+            ObjectType::Tag => "tag",
+        })
+    }
+}
+
+/// The id of an object with the given type and contents, framed as `<type> <len>\0<contents>`.
+pub fn object_id(format: ObjectFormat, object_type: ObjectType, contents: &[u8]) -> OkId {
+    let mut object = format!("{} {}\0", object_type, contents.len()).into_bytes();
+    object.extend_from_slice(contents);
+    format.hash(&object)
+}
+
+/// The id of a blob, as `git hash-object` computes it.
+pub fn blob_id(format: ObjectFormat, contents: &[u8]) -> OkId {
+    object_id(format, ObjectType::Blob, contents)
+}
+
+/// The id of a raw commit object, as printed by `git cat-file commit`.
+pub fn commit_id(format: ObjectFormat, contents: &[u8]) -> OkId {
+    object_id(format, ObjectType::Commit, contents)
+}
+
+/// Parse a full hex object id: 40 characters for SHA-1, 64 for SHA-256.
+pub fn parse_oid(s: &str) -> Result<OkId, Error> {
+    let hash_type = match s.len() {
+        #[allow(deprecated)]
+        40 => BinaryType::Sha1,
+        64 => BinaryType::Sha256,
+        _ => return Err(Error::InvalidLength),
+    };
+    OkId::from_digest_bytes(hash_type, &hex::decode(s)?)
+}
+
+impl OkId {
+    /// Format as a git hex object id.
+    pub fn to_git_oid(&self) -> Result<String, Error> {
+        ObjectFormat::of(self)?;
+        Ok(hex::encode(self.digest_bytes()))
+    }
+}
+
+/// The mode of a tree entry.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Mode {
+    /// Regular file
+    File,
+    /// Executable file
+    Executable,
+    /// Symbolic link
+    Symlink,
+    /// Subdirectory
+    Tree,
+    /// Submodule commit
+    Submodule,
+}
+
+impl Mode {
+    fn as_str(self) -> &'static str {
+        match self {
+            Mode::File => "100644",
+            Mode::Executable => "100755",
+            Mode::Symlink => "120000",
+            Mode::Tree => "40000",
+            Mode::Submodule => "160000",
+        }
+    }
+}
+
+/// Builds tree objects from entries, in git's canonical order.
+#[derive(Clone, Debug, Default)]
+pub struct TreeBuilder {
+    format: ObjectFormat,
+    entries: Vec<(String, Mode, OkId)>,
+}
+
+impl TreeBuilder {
+    /// Create an empty tree in `format`.
+    pub fn new(format: ObjectFormat) -> Self {
+        TreeBuilder {
+            format,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add an entry, replacing any entry with the same name.
+    ///
+    /// Fails with [`Error::InvalidType`] if `id` is not of this tree's object format and
+    /// [`Error::InvalidFormat`] for names git would refuse.
+    pub fn insert(&mut self, name: &str, mode: Mode, id: OkId) -> Result<&mut Self, Error> {
+        if ObjectFormat::of(&id)? != self.format {
+            return Err(Error::InvalidType);
+        }
+        if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\0']) {
+            return Err(Error::InvalidFormat);
+        }
+        self.entries.retain(|(n, _, _)| n != name);
+        self.entries.push((name.to_string(), mode, id));
+        Ok(self)
+    }
+
+    /// The serialized tree object contents.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        // Trees sort as if their name ended in `/`
+        entries.sort_by_cached_key(|(name, mode, _)| {
+            let mut key = name.as_bytes().to_vec();
+            if *mode == Mode::Tree {
+                key.push(b'/');
+            }
+            key
+        });
+        let mut out = Vec::new();
+        for (name, mode, id) in entries {
+            out.extend_from_slice(mode.as_str().as_bytes());
+            out.push(b' ');
+            out.extend_from_slice(name.as_bytes());
+            out.push(0);
+            out.extend_from_slice(&id.digest_bytes());
+        }
+        out
+    }
+
+    /// The id of the tree object.
+    pub fn id(&self) -> OkId {
+        object_id(self.format, ObjectType::Tree, &self.to_bytes())
+    }
+}
+
+/// Hash a directory the way `git add -A && git write-tree` would.
+///
+/// Regular files, executables and symlinks are included; `.git` and empty directories are
+/// skipped, and ignore files are not consulted.
+pub fn write_tree(path: impl AsRef<Path>, format: ObjectFormat) -> io::Result<OkId> {
+    Ok(tree(path.as_ref(), format)?.unwrap_or_else(|| TreeBuilder::new(format).id()))
+}
+
+fn tree(path: &Path, format: ObjectFormat) -> io::Result<Option<OkId>> {
+    let mut builder = TreeBuilder::new(format);
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "file name is not UTF-8",
+            ));
+        };
+        if name == ".git" {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        let (mode, id) = if file_type.is_dir() {
+            match tree(&entry.path(), format)? {
+                Some(id) => (Mode::Tree, id),
+                None => continue,
+            }
+        } else if file_type.is_symlink() {
+            let target = fs::read_link(entry.path())?;
+            let target = target.to_str().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "link target is not UTF-8")
+            })?;
+            (Mode::Symlink, blob_id(format, target.as_bytes()))
+        } else {
+            let mode = if is_executable(&entry.metadata()?) {
+                Mode::Executable
+            } else {
+                Mode::File
+            };
+            (mode, blob_id(format, &fs::read(entry.path())?))
+        };
+        builder
+            .insert(name, mode, id)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    }
+    Ok((!builder.entries.is_empty()).then(|| builder.id()))
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o100 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    false
+}
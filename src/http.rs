@@ -0,0 +1,538 @@
+//! [RFC 9530] digest fields and OkId entity tags, as typed headers for the `headers` crate.
+//!
+//! `Content-Digest` and `Repr-Digest` carry `sha-256` entries as OkIds. RFC 9530 also
+//! registers `sha-512`, which has no OkId kind; such entries are ignored when decoding, as
+//! the RFC requires of unsupported algorithms.
+//!
+//! Entity tags use the ASCII form of an OkId, e.g. `"1/2/<hex>"`, and `If-None-Match`
+//! values are compared as OkIds.
+//!
+//! [RFC 9530]: https://www.rfc-editor.org/rfc/rfc9530
+//!
+//! # Examples
+//! ```
+//! use headers::{ETag, HeaderMap, HeaderMapExt};
+//! use okid::{http::{ContentDigest, IfNoneMatch}, OkId};
+//!
+//! let body = b"{\"hello\": \"world\"}";
+//! let digest = ContentDigest::from_body(body);
+//!
+//! let mut response = HeaderMap::new();
+//! response.typed_insert(digest.clone());
+//! assert_eq!(
+//!     response["content-digest"],
+//!     "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:"
+//! );
+//! assert_eq!(response.typed_get::<ContentDigest>().unwrap(), digest);
+//! digest.verify(body).unwrap();
+//!
+//! let id = digest.ids()[0];
+//! response.typed_insert(ETag::from(id));
+//!
+//! let mut request = HeaderMap::new();
+//! request.insert("if-none-match", response["etag"].clone());
+//! assert!(request.typed_get::<IfNoneMatch>().unwrap().matches(&id));
+//! ```
+
+use base64::{
+    alphabet,
+    engine::{general_purpose::STANDARD, DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+use headers::{Header, HeaderName, HeaderValue};
+use sha2::Digest as _;
+
+use crate::{to_ascii, BinaryType, Error, OkId};
+
+static CONTENT_DIGEST: HeaderName = HeaderName::from_static("content-digest");
+static REPR_DIGEST: HeaderName = HeaderName::from_static("repr-digest");
+static WANT_CONTENT_DIGEST: HeaderName = HeaderName::from_static("want-content-digest");
+
+const SHA256: &str = "sha-256";
+
+/// Structured field byte sequences may omit padding.
+const BYTE_SEQUENCE: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+macro_rules! digest_header {
+    ($name:ident, $header:ident, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// Always holds at least one `sha-256` OkId.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct $name(Vec<OkId>);
+
+        impl $name {
+            /// A field with a single SHA-256 OkId.
+            ///
+            /// Fails with [`Error::InvalidType`] for other kinds.
+            pub fn new(id: OkId) -> Result<Self, Error> {
+                match id.hash_type {
+                    BinaryType::Sha256 => Ok($name(vec![id])),
+                    _ => Err(Error::InvalidType),
+                }
+            }
+
+            /// Hash `body` with SHA-256.
+            pub fn from_body(body: &[u8]) -> Self {
+                $name(vec![sha2::Sha256::new_with_prefix(body).into()])
+            }
+
+            /// The OkIds in the field.
+            pub fn ids(&self) -> &[OkId] {
+                &self.0
+            }
+
+            /// Check `body` against every entry.
+            pub fn verify(&self, body: &[u8]) -> Result<(), Error> {
+                verify(&self.0, body)
+            }
+        }
+
+        impl Header for $name {
+            fn name() -> &'static HeaderName {
+                &$header
+            }
+
+            fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(
+                values: &mut I,
+            ) -> Result<Self, headers::Error> {
+                decode_digests(values).map($name)
+            }
+
+            fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+                values.extend(std::iter::once(encode_digests(&self.0)));
+            }
+        }
+    };
+}
+
+digest_header!(
+    ContentDigest,
+    CONTENT_DIGEST,
+    "`Content-Digest`: the digest of the message content as sent."
+);
+digest_header!(
+    ReprDigest,
+    REPR_DIGEST,
+    "`Repr-Digest`: the digest of the selected representation, before content coding."
+);
+
+fn verify(ids: &[OkId], body: &[u8]) -> Result<(), Error> {
+    let actual: OkId = sha2::Sha256::new_with_prefix(body).into();
+    match ids.iter().find(|id| **id != actual) {
+        Some(expected) => Err(Error::DigestMismatch {
+            expected: Box::new(*expected),
+            actual: Box::new(actual),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn decode_digests<'i>(
+    values: &mut impl Iterator<Item = &'i HeaderValue>,
+) -> Result<Vec<OkId>, headers::Error> {
+    let ids = decode_dictionary(values)?
+        .into_iter()
+        .filter(|(key, _)| key == SHA256)
+        .map(|(_, item)| match item {
+            Item::Bytes(bytes) => OkId::from_digest_bytes(BinaryType::Sha256, &bytes)
+                .map_err(|_| headers::Error::invalid()),
+            _ => Err(headers::Error::invalid()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if ids.is_empty() {
+        return Err(headers::Error::invalid());
+    }
+    Ok(ids)
+}
+
+fn encode_digests(ids: &[OkId]) -> HeaderValue {
+    let value = ids
+        .iter()
+        .map(|id| format!("{}=:{}:", SHA256, STANDARD.encode(id.digest_bytes())))
+        .collect::<Vec<_>>()
+        .join(", ");
+    HeaderValue::from_str(&value).expect("base64 is a valid header value")
+}
+
+/// `Want-Content-Digest`: the algorithms a client would like, with preferences from 0 (not
+/// acceptable) to 10.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WantContentDigest(Vec<(String, u8)>);
+
+impl WantContentDigest {
+    /// Create a field from `(algorithm, preference)` pairs.
+    ///
+    /// Fails with [`Error::InvalidFormat`] for preferences above 10 or invalid algorithm names.
+    pub fn new<S: Into<String>>(
+        preferences: impl IntoIterator<Item = (S, u8)>,
+    ) -> Result<Self, Error> {
+        let preferences = preferences
+            .into_iter()
+            .map(|(algorithm, preference)| (algorithm.into(), preference))
+            .collect::<Vec<_>>();
+        if preferences
+            .iter()
+            .any(|(algorithm, preference)| *preference > 10 || !is_key(algorithm))
+        {
+            return Err(Error::InvalidFormat);
+        }
+        Ok(WantContentDigest(preferences))
+    }
+
+    /// The preference for `algorithm`, if listed.
+    pub fn preference(&self, algorithm: &str) -> Option<u8> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(name, _)| name == algorithm)
+            .map(|(_, preference)| *preference)
+    }
+
+    /// Whether the client accepts a `sha-256` [`ContentDigest`].
+    pub fn accepts_sha256(&self) -> bool {
+        self.preference(SHA256)
+            .is_some_and(|preference| preference > 0)
+    }
+}
+
+impl Header for WantContentDigest {
+    fn name() -> &'static HeaderName {
+        &WANT_CONTENT_DIGEST
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(
+        values: &mut I,
+    ) -> Result<Self, headers::Error> {
+        decode_dictionary(values)?
+            .into_iter()
+            .map(|(key, item)| match item {
+                Item::Integer(preference @ 0..=10) => Ok((key, preference as u8)),
+                _ => Err(headers::Error::invalid()),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(WantContentDigest)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let value = self
+            .0
+            .iter()
+            .map(|(algorithm, preference)| format!("{}={}", algorithm, preference))
+            .collect::<Vec<_>>()
+            .join(", ");
+        values.extend(std::iter::once(
+            HeaderValue::from_str(&value).expect("keys are validated"),
+        ));
+    }
+}
+
+impl From<OkId> for headers::ETag {
+    /// A strong entity tag of the ASCII form of `id`.
+    fn from(id: OkId) -> Self {
+        format!("\"{}\"", to_ascii(id))
+            .parse()
+            .expect("the ASCII form is a valid entity tag")
+    }
+}
+
+impl TryFrom<&headers::ETag> for OkId {
+    type Error = Error;
+
+    fn try_from(etag: &headers::ETag) -> Result<Self, Self::Error> {
+        let mut values = Vec::with_capacity(1);
+        etag.encode(&mut values);
+        let value = values.first().ok_or(Error::InvalidFormat)?;
+        let (_, tag) = entity_tags(value.as_bytes())
+            .next()
+            .ok_or(Error::InvalidFormat)??;
+        parse_tag(tag)
+    }
+}
+
+/// `If-None-Match`, with each entity tag parsed as an OkId.
+///
+/// Tags that are not OkIds can never match and are dropped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IfNoneMatch {
+    /// `*`
+    Any,
+    /// A list of entity tags, strong or weak
+    Ids(Vec<OkId>),
+}
+
+impl IfNoneMatch {
+    /// Whether a response with an ETag of `id` is unchanged, so a GET may answer 304.
+    ///
+    /// Uses the weak comparison RFC 9110 specifies for `If-None-Match`.
+    pub fn matches(&self, id: &OkId) -> bool {
+        match self {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::Ids(ids) => ids.contains(id),
+        }
+    }
+}
+
+impl Header for IfNoneMatch {
+    fn name() -> &'static HeaderName {
+        &http::header::IF_NONE_MATCH
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(
+        values: &mut I,
+    ) -> Result<Self, headers::Error> {
+        let mut ids = Vec::new();
+        let mut any = false;
+        for value in values {
+            if value.as_bytes().trim_ascii() == b"*" {
+                any = true;
+                continue;
+            }
+            for tag in entity_tags(value.as_bytes()) {
+                let (_, tag) = tag.map_err(|_| headers::Error::invalid())?;
+                if let Ok(id) = parse_tag(tag) {
+                    ids.push(id);
+                }
+            }
+        }
+        Ok(if any {
+            IfNoneMatch::Any
+        } else {
+            IfNoneMatch::Ids(ids)
+        })
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let value = match self {
+            IfNoneMatch::Any => "*".to_string(),
+            IfNoneMatch::Ids(ids) => ids
+                .iter()
+                .map(|id| format!("\"{}\"", to_ascii(*id)))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        values.extend(std::iter::once(
+            HeaderValue::from_str(&value).expect("the ASCII form is a valid entity tag"),
+        ));
+    }
+}
+
+/// An entity tag's opaque part as an OkId, in ASCII or display form.
+fn parse_tag(tag: &[u8]) -> Result<OkId, Error> {
+    let tag = std::str::from_utf8(tag).map_err(|_| Error::InvalidFormat)?;
+    if tag.contains('/') {
+        return OkId::from_ascii(tag);
+    }
+    tag.parse()
+}
+
+/// The `(weak, opaque tag)` pairs of a comma separated list of entity tags.
+fn entity_tags(s: &[u8]) -> impl Iterator<Item = Result<(bool, &[u8]), Error>> {
+    let mut rest = s;
+    std::iter::from_fn(move || {
+        rest = rest.trim_ascii_start();
+        if rest.is_empty() {
+            return None;
+        }
+        let (weak, tag) = match rest.strip_prefix(b"W/") {
+            Some(tag) => (true, tag),
+            None => (false, rest),
+        };
+        let Some(tag) = tag.strip_prefix(b"\"") else {
+            rest = &[];
+            return Some(Err(Error::InvalidFormat));
+        };
+        let Some(end) = tag.iter().position(|b| *b == b'"') else {
+            rest = &[];
+            return Some(Err(Error::InvalidFormat));
+        };
+        let after = tag[end + 1..].trim_ascii_start();
+        rest = match after.strip_prefix(b",") {
+            Some(after) => after,
+            None if after.is_empty() => after,
+            None => {
+                rest = &[];
+                return Some(Err(Error::InvalidFormat));
+            }
+        };
+        Some(Ok((weak, &tag[..end])))
+    })
+}
+
+/// A dictionary member value; only the kinds digest fields use are kept.
+enum Item {
+    Bytes(Vec<u8>),
+    Integer(i64),
+    Other,
+}
+
+/// Parse an RFC 8941 dictionary spread over one or more field lines. Later duplicate keys
+/// replace earlier ones.
+fn decode_dictionary<'i>(
+    values: &mut impl Iterator<Item = &'i HeaderValue>,
+) -> Result<Vec<(String, Item)>, headers::Error> {
+    let combined = values
+        .map(|value| value.to_str().map_err(|_| headers::Error::invalid()))
+        .collect::<Result<Vec<_>, _>>()?
+        .join(",");
+    let mut parser = Parser(combined.trim_matches(' ').as_bytes());
+    let mut members: Vec<(String, Item)> = Vec::new();
+    if parser.0.is_empty() {
+        return Err(headers::Error::invalid());
+    }
+    loop {
+        let key = parser.key().ok_or_else(headers::Error::invalid)?;
+        let item = if parser.eat(b'=') {
+            parser.item().ok_or_else(headers::Error::invalid)?
+        } else {
+            parser.parameters().ok_or_else(headers::Error::invalid)?;
+            Item::Other
+        };
+        members.retain(|(k, _)| *k != key);
+        members.push((key, item));
+        parser.whitespace();
+        if parser.0.is_empty() {
+            return Ok(members);
+        }
+        if !parser.eat(b',') {
+            return Err(headers::Error::invalid());
+        }
+        parser.whitespace();
+        if parser.0.is_empty() {
+            return Err(headers::Error::invalid());
+        }
+    }
+}
+
+fn is_key(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    matches!(bytes.next(), Some(b'a'..=b'z' | b'*'))
+        && bytes.all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b'*'))
+}
+
+struct Parser<'a>(&'a [u8]);
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.0.first().copied()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.0 = &self.0[1..];
+            true
+        } else {
+            false
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(u8) -> bool) -> &'a [u8] {
+        let end = self.0.iter().position(|b| !f(*b)).unwrap_or(self.0.len());
+        let (taken, rest) = self.0.split_at(end);
+        self.0 = rest;
+        taken
+    }
+
+    fn whitespace(&mut self) {
+        self.take_while(|b| b == b' ' || b == b'\t');
+    }
+
+    fn key(&mut self) -> Option<String> {
+        if !matches!(self.peek(), Some(b'a'..=b'z' | b'*')) {
+            return None;
+        }
+        let key =
+            self.take_while(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b'*'));
+        Some(String::from_utf8_lossy(key).into_owned())
+    }
+
+    /// An item or inner list, with its parameters.
+    fn item(&mut self) -> Option<Item> {
+        let item = if self.eat(b'(') {
+            loop {
+                self.take_while(|b| b == b' ');
+                if self.eat(b')') {
+                    break;
+                }
+                self.bare_item()?;
+                self.parameters()?;
+                if !matches!(self.peek(), Some(b' ' | b')')) {
+                    return None;
+                }
+            }
+            Item::Other
+        } else {
+            self.bare_item()?
+        };
+        self.parameters()?;
+        Some(item)
+    }
+
+    fn parameters(&mut self) -> Option<()> {
+        while self.eat(b';') {
+            self.take_while(|b| b == b' ');
+            self.key()?;
+            if self.eat(b'=') {
+                self.bare_item()?;
+            }
+        }
+        Some(())
+    }
+
+    fn bare_item(&mut self) -> Option<Item> {
+        match self.peek()? {
+            b':' => {
+                self.eat(b':');
+                let encoded = self.take_while(|b| b != b':');
+                let bytes = BYTE_SEQUENCE.decode(encoded).ok()?;
+                self.eat(b':').then_some(Item::Bytes(bytes))
+            }
+            b'-' | b'0'..=b'9' => {
+                let negative = self.eat(b'-');
+                let digits = self.take_while(|b| b.is_ascii_digit());
+                if digits.is_empty() || digits.len() > 15 {
+                    return None;
+                }
+                if self.eat(b'.') {
+                    let fraction = self.take_while(|b| b.is_ascii_digit());
+                    return (!fraction.is_empty() && fraction.len() <= 3 && digits.len() <= 12)
+                        .then_some(Item::Other);
+                }
+                let value: i64 = std::str::from_utf8(digits).ok()?.parse().ok()?;
+                Some(Item::Integer(if negative { -value } else { value }))
+            }
+            b'"' => {
+                self.eat(b'"');
+                loop {
+                    match self.peek()? {
+                        b'"' => {
+                            self.eat(b'"');
+                            return Some(Item::Other);
+                        }
+                        b'\\' => {
+                            self.eat(b'\\');
+                            if !matches!(self.peek()?, b'"' | b'\\') {
+                                return None;
+                            }
+                            self.0 = &self.0[1..];
+                        }
+                        0x20..=0x7e => self.0 = &self.0[1..],
+                        _ => return None,
+                    }
+                }
+            }
+            b'?' => {
+                self.eat(b'?');
+                (self.eat(b'0') || self.eat(b'1')).then_some(Item::Other)
+            }
+            b'*' | b'A'..=b'Z' | b'a'..=b'z' => {
+                self.take_while(|b| b.is_ascii_alphanumeric() || b":/!#$%&'*+-.^_`|~".contains(&b));
+                Some(Item::Other)
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! iroh blob tickets made of a node OkId and a content OkId.
+//!
+//! # Examples
+//! ```
+//! use okid::OkId;
+//!
+//! let node = OkId::from(&ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]).verifying_key());
+//! let content: OkId = "bːaf1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+//!     .parse()
+//!     .unwrap();
+//!
+//! let ticket = okid::iroh::blob_ticket(&node, &content).unwrap();
+//! assert_eq!(okid::iroh::parse_blob_ticket(&ticket.to_string()).unwrap(), (node, content));
+//! ```
+
+use iroh::{NodeAddr, NodeId};
+use iroh_blobs::{ticket::BlobTicket, BlobFormat, Hash};
+
+use crate::{Error, OkId};
+
+/// Build a ticket for the raw blob `content` served by `node`.
+///
+/// `node` must be a public key and `content` a BLAKE3 OkId. The ticket carries no relay or
+/// direct addresses, so the node is found through discovery.
+pub fn blob_ticket(node: &OkId, content: &OkId) -> Result<BlobTicket, Error> {
+    let node = NodeId::try_from(*node)?;
+    let hash = Hash::try_from(*content)?;
+    Ok(BlobTicket::new(NodeAddr::new(node), hash, BlobFormat::Raw))
+}
+
+/// The node and content OkIds of a ticket.
+pub fn blob_ticket_ids(ticket: &BlobTicket) -> (OkId, OkId) {
+    (ticket.node_addr().node_id.into(), ticket.hash().into())
+}
+
+/// Parse a `blob…` ticket string into its node and content OkIds.
+pub fn parse_blob_ticket(s: &str) -> Result<(OkId, OkId), Error> {
+    let ticket: BlobTicket = s.parse().map_err(|_| Error::InvalidFormat)?;
+    Ok(blob_ticket_ids(&ticket))
+}
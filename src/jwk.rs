@@ -0,0 +1,179 @@
+//! JSON Web Keys and RFC 7638 thumbprints.
+//!
+//! # Examples
+//! ```
+//! use okid::OkId;
+//!
+//! let key: OkId = "pːd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+//!     .parse()
+//!     .unwrap();
+//! let jwk = key.to_jwk().unwrap();
+//! assert_eq!(jwk.x, "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo");
+//! assert_eq!(OkId::try_from(&jwk).unwrap(), key);
+//! ```
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::Digest as _;
+
+use crate::{BinaryType, Error, OkId};
+
+/// A JSON Web Key.
+///
+/// Only the members needed to identify a public key are modelled; [`Jwk::thumbprint`] works
+/// for any `kty`, while conversion to an OkId is limited to `OKP` keys.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwk {
+    /// Key type, e.g. `OKP`
+    pub kty: String,
+    /// Curve, e.g. `Ed25519`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub crv: String,
+    /// Public key (OKP) or x coordinate (EC)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub x: String,
+    /// y coordinate (EC)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    /// Modulus (RSA)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// Exponent (RSA)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    /// Key id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    /// Algorithm, e.g. `EdDSA`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    /// Intended use, e.g. `sig`
+    #[serde(default, rename = "use", skip_serializing_if = "Option::is_none")]
+    pub use_: Option<String>,
+}
+
+impl Jwk {
+    /// The RFC 7638 thumbprint: SHA-256 over the required members in lexicographic order.
+    pub fn thumbprint(&self) -> Result<OkId, Error> {
+        let members: Vec<(&str, &str)> = match self.kty.as_str() {
+            "OKP" => vec![("crv", &self.crv), ("kty", &self.kty), ("x", &self.x)],
+            "EC" => vec![
+                ("crv", &self.crv),
+                ("kty", &self.kty),
+                ("x", &self.x),
+                ("y", self.y.as_deref().ok_or(Error::InvalidFormat)?),
+            ],
+            "RSA" => vec![
+                ("e", self.e.as_deref().ok_or(Error::InvalidFormat)?),
+                ("kty", &self.kty),
+                ("n", self.n.as_deref().ok_or(Error::InvalidFormat)?),
+            ],
+            _ => return Err(Error::InvalidType),
+        };
+        let canonical = members
+            .iter()
+            .map(|(name, value)| format!("\"{}\":{}", name, json_string(value)))
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(sha2::Sha256::new_with_prefix(format!("{{{}}}", canonical)).into())
+    }
+}
+
+impl TryFrom<&Jwk> for OkId {
+    type Error = Error;
+
+    fn try_from(jwk: &Jwk) -> Result<Self, Self::Error> {
+        let hash_type = match (jwk.kty.as_str(), jwk.crv.as_str()) {
+            ("OKP", "Ed25519") => BinaryType::PubKey,
+            ("OKP", "X25519") => BinaryType::X25519,
+            _ => return Err(Error::InvalidType),
+        };
+        let x = URL_SAFE_NO_PAD
+            .decode(&jwk.x)
+            .map_err(|_| Error::InvalidFormat)?;
+        OkId::from_digest_bytes(hash_type, &x)
+    }
+}
+
+impl OkId {
+    /// Convert this public key into an `OKP` JWK.
+    pub fn to_jwk(&self) -> Result<Jwk, Error> {
+        let crv = match self.hash_type {
+            BinaryType::PubKey => "Ed25519",
+            BinaryType::X25519 => "X25519",
+            _ => return Err(Error::InvalidType),
+        };
+        Ok(Jwk {
+            kty: "OKP".to_string(),
+            crv: crv.to_string(),
+            x: URL_SAFE_NO_PAD.encode(self.digest_bytes()),
+            ..Default::default()
+        })
+    }
+
+    /// The RFC 7638 thumbprint of this public key's JWK.
+    pub fn jwk_thumbprint(&self) -> Result<OkId, Error> {
+        self.to_jwk()?.thumbprint()
+    }
+}
+
+#[cfg(feature = "json")]
+impl OkId {
+    /// Serialize this public key as JWK JSON, using its thumbprint as `kid`.
+    pub fn to_jwk_json(&self) -> Result<String, Error> {
+        let mut jwk = self.to_jwk()?;
+        jwk.kid = Some(jwk.thumbprint()?.to_string());
+        serde_json::to_string(&jwk).map_err(|_| Error::InvalidFormat)
+    }
+
+    /// Parse a public key from JWK JSON.
+    pub fn from_jwk_json(s: &str) -> Result<OkId, Error> {
+        let jwk: Jwk = serde_json::from_str(s).map_err(|_| Error::InvalidFormat)?;
+        OkId::try_from(&jwk)
+    }
+}
+
+/// A JWK Set whose `kid`s are the OkId thumbprints of the keys.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwks {
+    /// The keys
+    pub keys: Vec<Jwk>,
+}
+
+impl Jwks {
+    /// Build a JWK Set from public keys, setting each `kid` to the key's thumbprint.
+    pub fn from_keys(keys: impl IntoIterator<Item = OkId>) -> Result<Self, Error> {
+        let keys = keys
+            .into_iter()
+            .map(|key| {
+                let mut jwk = key.to_jwk()?;
+                jwk.kid = Some(jwk.thumbprint()?.to_string());
+                Ok(jwk)
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Jwks { keys })
+    }
+
+    /// Find the key whose `kid` is `thumbprint`.
+    pub fn get(&self, thumbprint: &OkId) -> Option<&Jwk> {
+        self.keys
+            .iter()
+            .find(|jwk| jwk.kid.as_deref().and_then(|kid| kid.parse().ok()) == Some(*thumbprint))
+    }
+}
+
+/// Encode `s` as a JSON string literal.
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,217 @@
+//! Forgiving parsing for ids that people paste: [`OkId::parse_lenient`].
+//!
+//! [`FromStr`](std::str::FromStr) only accepts the display form. The lenient parser also
+//! accepts the forms enabled in [`LenientOptions`] and reports which one matched.
+//!
+//! # Examples
+//! ```
+//! use okid::{lenient::{Form, LenientOptions}, OkId};
+//!
+//! let options = LenientOptions::default();
+//! let parsed = OkId::parse_lenient(" \"Uː4D3881627191C1D4236405AC98409B01\"\n", &options).unwrap();
+//! assert_eq!(parsed.form, Form::Display);
+//! assert!(parsed.trimmed && parsed.lowercased);
+//!
+//! let parsed = OkId::parse_lenient("u:4d3881627191c1d4236405ac98409b01", &options).unwrap();
+//! assert_eq!(parsed.form, Form::AsciiSeparator);
+//! assert_eq!(parsed.id, OkId::parse_lenient(&parsed.id.to_bubblebabble(), &options).unwrap().id);
+//! ```
+
+use crate::{parse::parse_okid, Error, OkId, SEPARATOR};
+
+/// The forms [`OkId::parse_lenient`] accepts besides the display form.
+///
+/// The default accepts all of them; [`LenientOptions::strict`] accepts none.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LenientOptions {
+    /// Strip surrounding whitespace and one pair of matching quotes or backticks
+    pub trim: bool,
+    /// Accept ASCII `:` in place of the separator `ː`
+    pub ascii_separator: bool,
+    /// Accept uppercase type codes. Hex digits are case-insensitive in every form.
+    pub uppercase: bool,
+    /// Accept the path-safe `1/<type>/<hex>` form, with any sharding
+    pub ascii: bool,
+    /// Accept [display-safe](OkId::to_display_safe) secrets
+    pub display_safe: bool,
+    /// Accept [bubblebabble](OkId::to_bubblebabble)
+    pub bubblebabble: bool,
+}
+
+impl Default for LenientOptions {
+    fn default() -> Self {
+        LenientOptions {
+            trim: true,
+            ascii_separator: true,
+            uppercase: true,
+            ascii: true,
+            display_safe: true,
+            bubblebabble: true,
+        }
+    }
+}
+
+impl LenientOptions {
+    /// Accept only the display form, like [`FromStr`](std::str::FromStr).
+    pub fn strict() -> Self {
+        LenientOptions {
+            trim: false,
+            ascii_separator: false,
+            uppercase: false,
+            ascii: false,
+            display_safe: false,
+            bubblebabble: false,
+        }
+    }
+}
+
+/// The form an id matched in [`OkId::parse_lenient`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Form {
+    /// `<type>ː<hex>`
+    Display,
+    /// `<type>:<hex>`
+    AsciiSeparator,
+    /// `1/<type>/<hex>`, possibly sharded
+    Ascii,
+    /// A display-safe secret
+    DisplaySafe,
+    /// Bubblebabble
+    Bubblebabble,
+}
+
+/// The result of [`OkId::parse_lenient`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Lenient {
+    /// The parsed id
+    pub id: OkId,
+    /// The form it matched
+    pub form: Form,
+    /// Whether whitespace or quotes were stripped
+    pub trimmed: bool,
+    /// Whether uppercase had to be folded
+    pub lowercased: bool,
+}
+
+impl OkId {
+    /// Parse an id in any of the forms enabled in `options`, reporting which one matched.
+    ///
+    /// If no form matches, the error is that of parsing the (trimmed) input as the display
+    /// form.
+    pub fn parse_lenient(s: &str, options: &LenientOptions) -> Result<Lenient, Error> {
+        let (s, trimmed) = if options.trim {
+            let t = trim(s);
+            (t, t.len() != s.len())
+        } else {
+            (s, false)
+        };
+        let lenient = |id, form, lowercased| Lenient {
+            id,
+            form,
+            trimmed,
+            lowercased,
+        };
+        let error = match textual(s, options) {
+            Ok((id, form)) => return Ok(lenient(id, form, false)),
+            Err(e) => e,
+        };
+        if options.uppercase && s.bytes().any(|b| b.is_ascii_uppercase()) {
+            if let Ok((id, form)) = textual(&s.to_ascii_lowercase(), options) {
+                return Ok(lenient(id, form, true));
+            }
+        }
+        if options.display_safe {
+            if let Some(id) = OkId::from_display_safe(s) {
+                return Ok(lenient(id, Form::DisplaySafe, false));
+            }
+        }
+        if options.bubblebabble {
+            let id = debabble(s)
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .and_then(|display| parse_okid(&display).ok());
+            if let Some(id) = id {
+                return Ok(lenient(id, Form::Bubblebabble, false));
+            }
+        }
+        Err(error)
+    }
+}
+
+/// Strip whitespace and then one pair of matching quotes, and whitespace inside them.
+fn trim(s: &str) -> &str {
+    let s = s.trim();
+    for quote in ['"', '\'', '`'] {
+        if let Some(inner) = s.strip_prefix(quote).and_then(|s| s.strip_suffix(quote)) {
+            return inner.trim();
+        }
+    }
+    s
+}
+
+/// The display, ASCII separator and path-safe forms.
+fn textual(s: &str, options: &LenientOptions) -> Result<(OkId, Form), Error> {
+    let error = match parse_okid(s) {
+        Ok(id) => return Ok((id, Form::Display)),
+        Err(e) => e,
+    };
+    if options.ascii_separator {
+        let mut chars = s.chars();
+        if let (Some(code), Some(':')) = (chars.next(), chars.next()) {
+            if let Ok(id) = parse_okid(&format!("{code}{SEPARATOR}{}", chars.as_str())) {
+                return Ok((id, Form::AsciiSeparator));
+            }
+        }
+    }
+    if options.ascii && s.contains('/') {
+        if let Ok(id) = OkId::from_ascii(s) {
+            return Ok((id, Form::Ascii));
+        }
+    }
+    Err(error)
+}
+
+const VOWELS: &[u8; 6] = b"aeiouy";
+const CONSONANTS: &[u8; 17] = b"bcdfghklmnprstvzx";
+
+/// Decode checksummed bubblebabble, the inverse of [`bubblebabble::bubblebabble`].
+fn debabble(s: &str) -> Option<Vec<u8>> {
+    let s = s.as_bytes().strip_prefix(b"x")?.strip_suffix(b"x")?;
+    let vowel = |c: u8| VOWELS.iter().position(|v| *v == c);
+    let consonant = |c: u8| CONSONANTS.iter().position(|v| *v == c);
+    // The three symbols of a byte with the checksum seed folded into the outer two
+    let byte = |chunk: &[u8], seed: usize| -> Option<u8> {
+        let high = (vowel(chunk[0])? + 6 - seed % 6) % 6;
+        let middle = consonant(chunk[1])?;
+        let low = (vowel(chunk[2])? + 6 - (seed / 6) % 6) % 6;
+        (high < 4 && middle < 16 && low < 4).then_some((high << 6 | middle << 2 | low) as u8)
+    };
+
+    let mut bytes = Vec::with_capacity(s.len() / 3);
+    let mut seed = 1;
+    let mut rest = s;
+    while rest.len() > 3 {
+        let (chunk, tail) = rest.split_at_checked(6)?;
+        if chunk[4] != b'-' {
+            return None;
+        }
+        let first = byte(chunk, seed)?;
+        let (high, low) = (consonant(chunk[3])?, consonant(chunk[5])?);
+        if high > 15 || low > 15 {
+            return None;
+        }
+        let second = (high << 4 | low) as u8;
+        bytes.extend([first, second]);
+        seed = (seed * 5 + first as usize * 7 + second as usize) % 36;
+        rest = tail;
+    }
+    // The last three symbols are either an odd byte or the checksum
+    let [a, b, c] = rest else {
+        return None;
+    };
+    if *b == b'x' {
+        (vowel(*a)? == seed % 6 && vowel(*c)? == seed / 6).then_some(bytes)
+    } else {
+        bytes.push(byte(rest, seed)?);
+        Some(bytes)
+    }
+}
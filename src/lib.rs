@@ -4,15 +4,26 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![deny(missing_docs)]
 
+mod ascii;
 mod binary_type;
+// The sha1 digest kind is deprecated but still supported
+#[allow(deprecated)]
 mod digest;
 mod error;
 pub mod hmac;
+#[cfg(feature = "jwk")]
+pub mod jwk;
+pub mod lenient;
+#[cfg(any(feature = "did", feature = "cid"))]
+mod multicodec;
+mod nix_store;
 mod okid;
 mod parse;
+mod scan;
 #[cfg(feature = "jsonschema")]
 mod schema;
-mod secret;
+pub mod secret;
+mod secret_key;
 mod size;
 mod uint;
 mod wireformat;
@@ -30,21 +41,54 @@ pub const SEPARATOR_BYTES: [u8; 2] = [203, 144];
 /// Separator bytes length for the OkId string representation
 pub const SEPARATOR_BYTES_LEN: usize = 2;
 
+pub use crate::ascii::{to_ascii_path, to_ascii_sharded, Sharding, ASCII_VERSION};
 pub use crate::error::Error;
 pub use crate::okid::{to_ascii, FromDigest, IntoOkId, OkId};
 pub use crate::parse::const_parse_okid;
+pub use crate::scan::{scan, scan_reader, ScanReader};
+pub use crate::secret::{redact_secrets, remove_secrets, scan_secrets, scan_secrets_reader};
 
 pub(crate) use crate::binary_type::BinaryType;
 pub(crate) use crate::digest::Digest;
 pub(crate) use crate::parse::hex_to_byte;
 
+#[cfg(feature = "axum")]
+pub mod axum;
 #[cfg(feature = "blake3")]
 /// blake3 module
 pub mod blake3;
+#[cfg(feature = "cid")]
+pub mod cid;
+#[cfg(any(feature = "sha2", feature = "blake3"))]
+pub mod commitment;
+#[cfg(feature = "did")]
+pub mod did;
+#[cfg(feature = "digest-set")]
+pub mod digest_set;
 /// fingerprint module
 pub mod fingerprint;
+#[cfg(feature = "git")]
+pub mod git;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "iroh")]
+pub mod iroh;
+#[cfg(feature = "ni")]
+pub mod ni;
+#[cfg(feature = "nix")]
+pub mod nix;
+#[cfg(feature = "oci")]
+pub mod oci;
+/// p256 module
+pub mod p256;
+#[cfg(feature = "pkarr")]
+pub mod pkarr;
 /// pubkey module
 pub mod pub_key;
+#[cfg(feature = "seal")]
+pub mod seal;
+/// secp256k1 module
+pub mod secp256k1;
 #[deprecated(
     since = "0.14.0",
     note = "Sha1 is not considered secure anymore, use sha2 or sha3 instead"
@@ -58,12 +102,22 @@ pub mod sha2;
 #[cfg(feature = "sha3")]
 /// sha3 module
 pub mod sha3;
+pub mod signature;
+#[cfg(feature = "sri")]
+pub mod sri;
+#[cfg(feature = "ssh")]
+pub mod ssh;
 #[cfg(feature = "ulid")]
 /// ulid module
 pub mod ulid;
+pub mod url;
 #[cfg(feature = "uuid")]
 /// uuid module
 pub mod uuid;
+/// x25519 module
+pub mod x25519;
+#[cfg(feature = "x509")]
+pub mod x509;
 
 mod serde;
 mod wasm;
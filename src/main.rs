@@ -1,5 +1,8 @@
 use {
-    okid::OkId,
+    okid::{
+        lenient::{Form, Lenient, LenientOptions},
+        OkId,
+    },
     okstd::prelude::*,
     std::io::{self, Read},
 };
@@ -10,17 +13,20 @@ async fn main() {
     let mut input = String::new();
     io::stdin().read_to_string(&mut input).unwrap();
     let input = input.trim();
-    let second_input = input;
 
-    match (input.parse::<OkId>(), OkId::from_display_safe(second_input)) {
-        (Ok(id), None) => {
-            println!("{}", id.to_display_safe());
-        }
-        (Err(_), Some(id)) => {
+    match OkId::parse_lenient(input, &LenientOptions::default()) {
+        Ok(Lenient {
+            id,
+            form: Form::DisplaySafe,
+            ..
+        }) => {
             println!("{}", id);
         }
-        _ => {
-            eprintln!("Invalid input: {}", input);
+        Ok(Lenient { id, .. }) => {
+            println!("{}", id.to_display_safe());
+        }
+        Err(e) => {
+            eprintln!("Invalid input: {}: {}", input, e);
             std::process::exit(1);
         }
     }
@@ -0,0 +1,110 @@
+//! Multicodec codes and unsigned varints shared by the multiformat encodings.
+
+use crate::BinaryType;
+
+/// Multicodec codes of the public key kinds.
+#[cfg(feature = "did")]
+const KEY_CODECS: &[(BinaryType, u64)] = &[
+    (BinaryType::PubKey, 0xed),
+    (BinaryType::X25519, 0xec),
+    (BinaryType::Secp256k1, 0xe7),
+    (BinaryType::P256, 0x1200),
+];
+
+/// The multicodec code of a public key kind.
+#[cfg(feature = "did")]
+pub(crate) fn key_codec(hash_type: BinaryType) -> Option<u64> {
+    KEY_CODECS
+        .iter()
+        .find(|(t, _)| *t == hash_type)
+        .map(|(_, code)| *code)
+}
+
+/// The public key kind of a multicodec code.
+#[cfg(feature = "did")]
+pub(crate) fn key_kind(code: u64) -> Option<BinaryType> {
+    KEY_CODECS.iter().find(|(_, c)| *c == code).map(|(t, _)| *t)
+}
+
+/// Multihash codes and names of the hash functions OkId knows about, supported or not.
+#[cfg(feature = "cid")]
+const HASH_CODES: &[(u64, &str)] = &[
+    (0x00, "identity"),
+    (0x11, "sha1"),
+    (0x12, "sha2-256"),
+    (0x13, "sha2-512"),
+    (0x14, "sha3-512"),
+    (0x15, "sha3-384"),
+    (0x16, "sha3-256"),
+    (0x17, "sha3-224"),
+    (0x1e, "blake3"),
+    (0x20, "sha2-384"),
+    (0xd5, "md5"),
+    (0xb220, "blake2b-256"),
+];
+
+/// The multihash code of a hash kind.
+#[cfg(feature = "cid")]
+pub(crate) fn hash_codec(hash_type: BinaryType) -> Option<u64> {
+    match hash_type {
+        #[cfg(feature = "sha1")]
+        BinaryType::Sha1 => Some(0x11),
+        #[cfg(feature = "sha2")]
+        BinaryType::Sha256 => Some(0x12),
+        #[cfg(feature = "sha3")]
+        BinaryType::Sha3_512 => Some(0x14),
+        #[cfg(feature = "blake3")]
+        BinaryType::Blake3 => Some(0x1e),
+        _ => None,
+    }
+}
+
+/// The hash kind of a multihash code.
+#[cfg(feature = "cid")]
+pub(crate) fn hash_kind(code: u64) -> Option<BinaryType> {
+    match code {
+        #[cfg(feature = "sha1")]
+        0x11 => Some(BinaryType::Sha1),
+        #[cfg(feature = "sha2")]
+        0x12 => Some(BinaryType::Sha256),
+        #[cfg(feature = "sha3")]
+        0x14 => Some(BinaryType::Sha3_512),
+        #[cfg(feature = "blake3")]
+        0x1e => Some(BinaryType::Blake3),
+        _ => None,
+    }
+}
+
+/// A readable name for a multihash code, for error messages.
+#[cfg(feature = "cid")]
+pub(crate) fn hash_name(code: u64) -> String {
+    HASH_CODES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| format!("multihash 0x{:x}", code))
+}
+
+/// Append `n` as an unsigned LEB128 varint.
+#[cfg(feature = "did")]
+pub(crate) fn write_varint(mut n: u64, out: &mut Vec<u8>) {
+    while n >= 0x80 {
+        out.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+/// Read an unsigned LEB128 varint, returning the value and the remaining bytes.
+#[cfg(feature = "did")]
+pub(crate) fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut n = 0u64;
+    // multiformats caps varints at 9 bytes
+    for (i, b) in bytes.iter().enumerate().take(9) {
+        n |= ((b & 0x7f) as u64) << (i * 7);
+        if b & 0x80 == 0 {
+            return Some((n, &bytes[i + 1..]));
+        }
+    }
+    None
+}
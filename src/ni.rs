@@ -0,0 +1,343 @@
+//! [RFC 6920] named information URIs: `ni://<authority>/<alg>;<base64url>` and the
+//! human-speakable `nih:<alg>;<hex>;<check digit>` form.
+//!
+//! Truncated SHA-256 names such as `sha-256-128` parse and can be [matched](NamedInfo::matches)
+//! against an OkId, but cannot become one: converting them fails with
+//! [`Error::UnsupportedAlgorithm`].
+//!
+//! [RFC 6920]: https://www.rfc-editor.org/rfc/rfc6920
+//!
+//! # Examples
+//! ```
+//! use okid::{ni::{Algorithm, NamedInfo}, OkId};
+//!
+//! let id = OkId::from_ni_uri("ni:///sha-256;f4OxZX_x_FO5LcGBSKHWXfwtSx-j1ncoSt3SABJtkGk").unwrap();
+//! assert_eq!(
+//!     id.to_ni_uri("example.com").unwrap(),
+//!     "ni://example.com/sha-256;f4OxZX_x_FO5LcGBSKHWXfwtSx-j1ncoSt3SABJtkGk"
+//! );
+//!
+//! let short = NamedInfo::truncated(&id, Algorithm::Sha256_120).unwrap();
+//! assert_eq!(short.to_nih_uri(), "nih:sha-256-120;7f83-b165-7ff1-fc53-b92d-c181-48a1-d6;8");
+//! assert!(short.matches(&id));
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+use crate::{BinaryType, Error, OkId};
+
+const NI: &str = "ni:";
+const NIH: &str = "nih:";
+
+/// A hash algorithm from the named information hash algorithm registry.
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// `sha-256`, suite 1
+    Sha256,
+    /// `sha-256-128`, suite 2
+    Sha256_128,
+    /// `sha-256-120`, suite 3
+    Sha256_120,
+    /// `sha-256-96`, suite 4
+    Sha256_96,
+    /// `sha-256-64`, suite 5
+    Sha256_64,
+    /// `sha-256-32`, suite 6
+    Sha256_32,
+    /// `sha3-512`, suite 12
+    #[cfg(feature = "sha3")]
+    Sha3_512,
+}
+
+const ALGORITHMS: &[Algorithm] = &[
+    Algorithm::Sha256,
+    Algorithm::Sha256_128,
+    Algorithm::Sha256_120,
+    Algorithm::Sha256_96,
+    Algorithm::Sha256_64,
+    Algorithm::Sha256_32,
+    #[cfg(feature = "sha3")]
+    Algorithm::Sha3_512,
+];
+
+impl Algorithm {
+    /// The registered name, e.g. `sha-256-128`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "sha-256",
+            Algorithm::Sha256_128 => "sha-256-128",
+            Algorithm::Sha256_120 => "sha-256-120",
+            Algorithm::Sha256_96 => "sha-256-96",
+            Algorithm::Sha256_64 => "sha-256-64",
+            Algorithm::Sha256_32 => "sha-256-32",
+            #[cfg(feature = "sha3")]
+            Algorithm::Sha3_512 => "sha3-512",
+        }
+    }
+
+    /// The registered suite id, usable in place of the name in `nih:` URIs.
+    pub fn suite(self) -> u8 {
+        match self {
+            Algorithm::Sha256 => 1,
+            Algorithm::Sha256_128 => 2,
+            Algorithm::Sha256_120 => 3,
+            Algorithm::Sha256_96 => 4,
+            Algorithm::Sha256_64 => 5,
+            Algorithm::Sha256_32 => 6,
+            #[cfg(feature = "sha3")]
+            Algorithm::Sha3_512 => 12,
+        }
+    }
+
+    /// The digest length in bytes.
+    pub fn digest_len(self) -> usize {
+        match self {
+            Algorithm::Sha256 => 32,
+            Algorithm::Sha256_128 => 16,
+            Algorithm::Sha256_120 => 15,
+            Algorithm::Sha256_96 => 12,
+            Algorithm::Sha256_64 => 8,
+            Algorithm::Sha256_32 => 4,
+            #[cfg(feature = "sha3")]
+            Algorithm::Sha3_512 => 64,
+        }
+    }
+
+    /// The OkId kind of the untruncated hash.
+    fn hash_type(self) -> BinaryType {
+        match self {
+            #[cfg(feature = "sha3")]
+            Algorithm::Sha3_512 => BinaryType::Sha3_512,
+            _ => BinaryType::Sha256,
+        }
+    }
+
+    fn is_truncated(self) -> bool {
+        matches!(
+            self,
+            Algorithm::Sha256_128
+                | Algorithm::Sha256_120
+                | Algorithm::Sha256_96
+                | Algorithm::Sha256_64
+                | Algorithm::Sha256_32
+        )
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    /// Parse a registered name or suite id.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ALGORITHMS
+            .iter()
+            .find(|algorithm| algorithm.name() == s || algorithm.suite().to_string() == s)
+            .copied()
+            .ok_or_else(|| Error::UnsupportedAlgorithm(s.to_string()))
+    }
+}
+
+/// A parsed `ni:` or `nih:` name.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NamedInfo {
+    /// Authority that can resolve the name, if any
+    pub authority: Option<String>,
+    /// Hash algorithm
+    pub algorithm: Algorithm,
+    /// Digest, already truncated for truncated algorithms
+    pub digest: Vec<u8>,
+}
+
+impl NamedInfo {
+    /// Name `id` with its full hash.
+    pub fn new(id: &OkId) -> Result<Self, Error> {
+        let algorithm = match id.hash_type {
+            BinaryType::Sha256 => Algorithm::Sha256,
+            #[cfg(feature = "sha3")]
+            BinaryType::Sha3_512 => Algorithm::Sha3_512,
+            _ => return Err(Error::InvalidType),
+        };
+        NamedInfo::truncated(id, algorithm)
+    }
+
+    /// Name `id` with `algorithm`, truncating its hash as needed.
+    ///
+    /// Fails with [`Error::InvalidType`] if `algorithm` is not a truncation of `id`'s hash.
+    pub fn truncated(id: &OkId, algorithm: Algorithm) -> Result<Self, Error> {
+        if id.hash_type != algorithm.hash_type() {
+            return Err(Error::InvalidType);
+        }
+        let mut digest = id.digest_bytes();
+        digest.truncate(algorithm.digest_len());
+        Ok(NamedInfo {
+            authority: None,
+            algorithm,
+            digest,
+        })
+    }
+
+    /// Set the authority.
+    pub fn with_authority(mut self, authority: impl Into<String>) -> Self {
+        self.authority = Some(authority.into());
+        self
+    }
+
+    /// Whether this name identifies `id`, comparing only the truncated prefix.
+    pub fn matches(&self, id: &OkId) -> bool {
+        id.hash_type == self.algorithm.hash_type() && id.digest_bytes().starts_with(&self.digest)
+    }
+
+    /// Format as `ni://<authority>/<alg>;<base64url>`.
+    pub fn to_ni_uri(&self) -> String {
+        format!(
+            "{}//{}/{};{}",
+            NI,
+            self.authority.as_deref().unwrap_or_default(),
+            self.algorithm,
+            URL_SAFE_NO_PAD.encode(&self.digest)
+        )
+    }
+
+    /// Format as `nih:<alg>;<hex>;<check digit>`, with the hex grouped in fours.
+    pub fn to_nih_uri(&self) -> String {
+        let hex = hex::encode(&self.digest);
+        let grouped = hex
+            .as_bytes()
+            .chunks(4)
+            .map(|chunk| std::str::from_utf8(chunk).expect("hex is ASCII"))
+            .collect::<Vec<_>>()
+            .join("-");
+        let authority = self
+            .authority
+            .as_ref()
+            .map(|authority| format!("//{}/", authority))
+            .unwrap_or_default();
+        format!(
+            "{}{}{};{};{}",
+            NIH,
+            authority,
+            self.algorithm,
+            grouped,
+            check_digit(&hex)
+        )
+    }
+
+    fn parse_ni(rest: &str) -> Result<Self, Error> {
+        let rest = rest.strip_prefix("//").ok_or(Error::InvalidFormat)?;
+        let (authority, path) = rest.split_once('/').ok_or(Error::InvalidFormat)?;
+        let path = path.split_once('?').map_or(path, |(path, _query)| path);
+        let (algorithm, value) = path.split_once(';').ok_or(Error::InvalidFormat)?;
+        let algorithm = ALGORITHMS
+            .iter()
+            .find(|a| a.name() == algorithm)
+            .copied()
+            .ok_or_else(|| Error::UnsupportedAlgorithm(algorithm.to_string()))?;
+        let digest = URL_SAFE_NO_PAD
+            .decode(value)
+            .map_err(|_| Error::InvalidFormat)?;
+        NamedInfo::from_parts(authority, algorithm, digest)
+    }
+
+    fn parse_nih(rest: &str) -> Result<Self, Error> {
+        let (authority, rest) = match rest.strip_prefix("//") {
+            Some(rest) => rest.split_once('/').ok_or(Error::InvalidFormat)?,
+            None => ("", rest),
+        };
+        let mut parts = rest.split(';');
+        let algorithm = parts
+            .next()
+            .ok_or(Error::InvalidFormat)?
+            .parse::<Algorithm>()?;
+        let value = parts.next().ok_or(Error::InvalidFormat)?.replace('-', "");
+        if let Some(check) = parts.next() {
+            if check != check_digit(&value).to_string() {
+                return Err(Error::InvalidFormat);
+            }
+        }
+        if parts.next().is_some() || value.bytes().any(|b| b.is_ascii_uppercase()) {
+            return Err(Error::InvalidFormat);
+        }
+        NamedInfo::from_parts(authority, algorithm, hex::decode(&value)?)
+    }
+
+    fn from_parts(authority: &str, algorithm: Algorithm, digest: Vec<u8>) -> Result<Self, Error> {
+        if digest.len() != algorithm.digest_len() {
+            return Err(Error::InvalidLength);
+        }
+        Ok(NamedInfo {
+            authority: (!authority.is_empty()).then(|| authority.to_string()),
+            algorithm,
+            digest,
+        })
+    }
+}
+
+impl fmt::Display for NamedInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_ni_uri())
+    }
+}
+
+impl FromStr for NamedInfo {
+    type Err = Error;
+
+    /// Parse an `ni:` or `nih:` URI. A `nih:` check digit is verified when present.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(rest) = s.strip_prefix(NIH) {
+            NamedInfo::parse_nih(rest)
+        } else if let Some(rest) = s.strip_prefix(NI) {
+            NamedInfo::parse_ni(rest)
+        } else {
+            Err(Error::InvalidFormat)
+        }
+    }
+}
+
+impl TryFrom<&NamedInfo> for OkId {
+    type Error = Error;
+
+    fn try_from(name: &NamedInfo) -> Result<Self, Self::Error> {
+        if name.algorithm.is_truncated() {
+            return Err(Error::UnsupportedAlgorithm(name.algorithm.to_string()));
+        }
+        OkId::from_digest_bytes(name.algorithm.hash_type(), &name.digest)
+    }
+}
+
+impl OkId {
+    /// Format as an `ni:` URI; pass an empty `authority` for `ni:///…`.
+    pub fn to_ni_uri(&self, authority: &str) -> Result<String, Error> {
+        Ok(NamedInfo::new(self)?.with_authority(authority).to_ni_uri())
+    }
+
+    /// Format as a `nih:` URI with a check digit.
+    pub fn to_nih_uri(&self) -> Result<String, Error> {
+        Ok(NamedInfo::new(self)?.to_nih_uri())
+    }
+
+    /// Parse an untruncated `ni:` or `nih:` URI.
+    pub fn from_ni_uri(s: &str) -> Result<OkId, Error> {
+        OkId::try_from(&s.parse::<NamedInfo>()?)
+    }
+}
+
+/// The Luhn mod 16 check digit of a hex string.
+fn check_digit(hex: &str) -> char {
+    let mut factor = 2;
+    let mut sum = 0;
+    for digit in hex.chars().rev().filter_map(|c| c.to_digit(16)) {
+        let addend = factor * digit;
+        factor = if factor == 2 { 1 } else { 2 };
+        sum += addend / 16 + addend % 16;
+    }
+    char::from_digit((16 - sum % 16) % 16, 16).expect("less than 16")
+}
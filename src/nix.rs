@@ -0,0 +1,246 @@
+//! Nix hash representations, store path hashes and NAR hashes.
+//!
+//! # Examples
+//! ```
+//! use okid::{nix::HashFormat, OkId};
+//!
+//! let empty: OkId = "2ːe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+//!     .parse()
+//!     .unwrap();
+//! let narinfo = empty.to_nix_hash(HashFormat::Nix32).unwrap();
+//! assert_eq!(narinfo, "sha256:0mdqa9w1p6cmli6976v4wi0sw9r4p5prkj7lzfd1877wk11c9c73");
+//! assert_eq!(OkId::from_nix_hash(&narinfo).unwrap(), empty);
+//! assert_eq!(
+//!     OkId::from_nix_hash("sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=").unwrap(),
+//!     empty
+//! );
+//! ```
+
+use std::{fs, io, path::Path};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sha2::Digest as _;
+
+use crate::{BinaryType, Digest, Error, OkId};
+
+const NIX32_ALPHABET: &[u8; 32] = b"0123456789abcdfghijklmnpqrsvwxyz";
+const STORE_DIR: &str = "/nix/store/";
+const STORE_HASH_LEN: usize = 20;
+
+/// How [`OkId::to_nix_hash`] encodes the digest, as in `nix hash convert --to`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum HashFormat {
+    /// `sha256:<hex>`
+    Base16,
+    /// `sha256:<nix32>`, the form used by narinfo files
+    #[default]
+    Nix32,
+    /// `sha256:<base64>`
+    Base64,
+    /// `sha256-<base64>`
+    Sri,
+}
+
+/// Encode bytes in Nix's base32 alphabet and bit order.
+pub fn to_nix32(bytes: &[u8]) -> String {
+    let len = (bytes.len() * 8).div_ceil(5);
+    (0..len)
+        .rev()
+        .map(|n| {
+            let bit = n * 5;
+            let (i, j) = (bit / 8, bit % 8);
+            let mut c = bytes[i] >> j;
+            if let Some(next) = bytes.get(i + 1) {
+                c |= next.checked_shl(8 - j as u32).unwrap_or(0);
+            }
+            NIX32_ALPHABET[(c & 0x1f) as usize] as char
+        })
+        .collect()
+}
+
+/// Decode a Nix base32 string.
+pub fn from_nix32(s: &str) -> Result<Vec<u8>, Error> {
+    let mut bytes = vec![0u8; s.len() * 5 / 8];
+    for (n, c) in s.bytes().rev().enumerate() {
+        let digit = NIX32_ALPHABET
+            .iter()
+            .position(|a| *a == c)
+            .ok_or(Error::InvalidFormat)? as u8;
+        let bit = n * 5;
+        let (i, j) = (bit / 8, bit % 8);
+        let carry = (digit as u16) << j;
+        match bytes.get_mut(i) {
+            Some(b) => *b |= carry as u8,
+            None if digit == 0 => continue,
+            None => return Err(Error::InvalidFormat),
+        }
+        match bytes.get_mut(i + 1) {
+            Some(b) => *b |= (carry >> 8) as u8,
+            None if carry >> 8 == 0 => {}
+            None => return Err(Error::InvalidFormat),
+        }
+    }
+    Ok(bytes)
+}
+
+impl OkId {
+    /// Format as a Nix hash, e.g. `sha256:<nix32>` for a narinfo `NarHash`.
+    pub fn to_nix_hash(&self, format: HashFormat) -> Result<String, Error> {
+        let algorithm = nix_algorithm(self.hash_type).ok_or(Error::InvalidType)?;
+        let bytes = self.digest_bytes();
+        Ok(match format {
+            HashFormat::Base16 => format!("{}:{}", algorithm, hex::encode(bytes)),
+            HashFormat::Nix32 => format!("{}:{}", algorithm, to_nix32(&bytes)),
+            HashFormat::Base64 => format!("{}:{}", algorithm, STANDARD.encode(bytes)),
+            HashFormat::Sri => format!("{}-{}", algorithm, STANDARD.encode(bytes)),
+        })
+    }
+
+    /// Parse a Nix hash in any of the [`HashFormat`]s.
+    ///
+    /// As in Nix, the encoding of `algo:` forms is told apart by length. `sha512` and `md5`
+    /// hashes are well formed but fail with [`Error::UnsupportedAlgorithm`].
+    pub fn from_nix_hash(s: &str) -> Result<OkId, Error> {
+        let (algorithm, encoded, sri) = match s.split_once(':') {
+            Some((algorithm, encoded)) => (algorithm, encoded, false),
+            None => {
+                let (algorithm, encoded) = s.split_once('-').ok_or(Error::InvalidFormat)?;
+                (algorithm, encoded, true)
+            }
+        };
+        let (hash_type, len) = match algorithm {
+            #[allow(deprecated)]
+            #[cfg(feature = "sha1")]
+            "sha1" => (BinaryType::Sha1, 20usize),
+            "sha256" => (BinaryType::Sha256, 32usize),
+            "sha512" | "md5" => return Err(Error::UnsupportedAlgorithm(algorithm.to_string())),
+            _ => return Err(Error::InvalidDigestType),
+        };
+        let bytes = if sri || encoded.len() == len.div_ceil(3) * 4 {
+            STANDARD.decode(encoded).map_err(|_| Error::InvalidFormat)?
+        } else if encoded.len() == len * 2 {
+            hex::decode(encoded)?
+        } else if encoded.len() == (len * 8).div_ceil(5) {
+            from_nix32(encoded)?
+        } else {
+            return Err(Error::InvalidLength);
+        };
+        OkId::from_digest_bytes(hash_type, &bytes)
+    }
+
+    /// Compress a SHA-256 OkId to a 160-bit store path hash, as Nix does for store paths.
+    pub fn to_nix_store_hash(&self) -> Result<OkId, Error> {
+        let Digest::Sha256(sha256) = self.digest else {
+            return Err(Error::InvalidType);
+        };
+        let mut compressed = [0u8; STORE_HASH_LEN];
+        for (i, b) in sha256.0.iter().enumerate() {
+            compressed[i % STORE_HASH_LEN] ^= b;
+        }
+        Ok(OkId {
+            hash_type: BinaryType::NixStore,
+            digest: Digest::NixStore(crate::nix_store::NixStore(compressed)),
+        })
+    }
+
+    /// Parse the hash part of a store path such as `/nix/store/<hash>-hello-2.12`.
+    ///
+    /// The store directory and name are optional, so a bare 32-character hash is accepted too.
+    pub fn from_nix_store_path(s: &str) -> Result<OkId, Error> {
+        let s = s.strip_prefix(STORE_DIR).unwrap_or(s);
+        let hash = s.split_once('-').map_or(s, |(hash, _)| hash);
+        if hash.len() != (STORE_HASH_LEN * 8).div_ceil(5) {
+            return Err(Error::InvalidLength);
+        }
+        OkId::from_digest_bytes(BinaryType::NixStore, &from_nix32(hash)?)
+    }
+
+    /// Format a store path hash as the 32-character nix32 prefix of a store path.
+    pub fn to_nix_store_path_hash(&self) -> Result<String, Error> {
+        match self.digest {
+            Digest::NixStore(hash) => Ok(to_nix32(&hash.0)),
+            _ => Err(Error::InvalidType),
+        }
+    }
+}
+
+/// The store path hash of a store path fingerprint, e.g.
+/// `source:sha256:<hex NAR hash>:/nix/store:<name>`.
+pub fn store_path_hash(fingerprint: &str) -> OkId {
+    OkId::from(sha2::Sha256::new_with_prefix(fingerprint))
+        .to_nix_store_hash()
+        .expect("SHA-256 OkIds compress")
+}
+
+fn nix_algorithm(hash_type: BinaryType) -> Option<&'static str> {
+    match hash_type {
+        #[allow(deprecated)]
+        #[cfg(feature = "sha1")]
+        BinaryType::Sha1 => Some("sha1"),
+        BinaryType::Sha256 => Some("sha256"),
+        _ => None,
+    }
+}
+
+/// The SHA-256 of the NAR serialisation of a file, symlink or directory, as `nix-hash --type
+/// sha256 <path>` and narinfo `NarHash` compute it.
+pub fn nar_hash(path: impl AsRef<Path>) -> io::Result<OkId> {
+    let mut hasher = sha2::Sha256::new();
+    write_str(&mut hasher, b"nix-archive-1");
+    write_nar(&mut hasher, path.as_ref())?;
+    Ok(hasher.into())
+}
+
+fn write_nar(hasher: &mut sha2::Sha256, path: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    write_str(hasher, b"(");
+    write_str(hasher, b"type");
+    if metadata.is_symlink() {
+        write_str(hasher, b"symlink");
+        write_str(hasher, b"target");
+        write_str(hasher, fs::read_link(path)?.as_os_str().as_encoded_bytes());
+    } else if metadata.is_dir() {
+        write_str(hasher, b"directory");
+        let mut entries = fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by(|a, b| a.as_encoded_bytes().cmp(b.as_encoded_bytes()));
+        for name in entries {
+            write_str(hasher, b"entry");
+            write_str(hasher, b"(");
+            write_str(hasher, b"name");
+            write_str(hasher, name.as_encoded_bytes());
+            write_str(hasher, b"node");
+            write_nar(hasher, &path.join(&name))?;
+            write_str(hasher, b")");
+        }
+    } else {
+        write_str(hasher, b"regular");
+        if is_executable(&metadata) {
+            write_str(hasher, b"executable");
+            write_str(hasher, b"");
+        }
+        write_str(hasher, b"contents");
+        write_str(hasher, &fs::read(path)?);
+    }
+    write_str(hasher, b")");
+    Ok(())
+}
+
+/// A length prefixed string, padded to 8 bytes.
+fn write_str(hasher: &mut sha2::Sha256, s: &[u8]) {
+    hasher.update((s.len() as u64).to_le_bytes());
+    hasher.update(s);
+    hasher.update(&[0u8; 8][..(8 - s.len() % 8) % 8]);
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o100 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    false
+}
@@ -0,0 +1,52 @@
+use {
+    crate::{hex_to_byte, Error},
+    std::{fmt::Display, str::FromStr},
+    zerocopy::{ByteEq, ByteHash, FromBytes, Immutable, IntoBytes, Unaligned},
+};
+
+pub(crate) const NIX_STORE_LEN: usize = 20;
+
+/// A Nix store path hash: SHA-256 compressed to 160 bits.
+#[derive(Copy, Clone, Debug, ByteEq, Immutable, IntoBytes, ByteHash, FromBytes, Unaligned)]
+#[repr(C)]
+pub(super) struct NixStore(pub(crate) [u8; NIX_STORE_LEN]);
+
+impl Display for NixStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let buf = &hex::encode(self.0);
+        f.write_str(buf)?;
+        Ok(())
+    }
+}
+
+impl FromStr for NixStore {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let buf = hex::decode(s)?;
+        if buf.len() != NIX_STORE_LEN {
+            return Err(Error::InvalidLength);
+        }
+        let mut key = [0; NIX_STORE_LEN];
+        key.copy_from_slice(&buf);
+        Ok(NixStore(key))
+    }
+}
+
+pub(crate) const fn parse_nix_store_bytes(bytes: &[u8], start: usize) -> Option<NixStore> {
+    let mut result = [0u8; NIX_STORE_LEN];
+    let mut i = 0;
+    while i < NIX_STORE_LEN * 2 {
+        let high = match hex_to_byte(bytes[start + i]) {
+            Some(b) => b,
+            None => return None,
+        };
+        let low = match hex_to_byte(bytes[start + i + 1]) {
+            Some(b) => b,
+            None => return None,
+        };
+        result[i / 2] = (high << 4) | low;
+        i += 2;
+    }
+    Some(NixStore(result))
+}
@@ -0,0 +1,248 @@
+//! OCI content digests and descriptors.
+//!
+//! Digests follow the [OCI image spec] grammar `algorithm ":" encoded`. `sha256` and `blake3`
+//! digests map to OkIds; other well formed algorithms, including `sha512`, are rejected with
+//! [`Error::UnsupportedAlgorithm`].
+//!
+//! [OCI image spec]: https://github.com/opencontainers/image-spec/blob/main/descriptor.md#digests
+//!
+//! # Examples
+//! ```
+//! use okid::{oci::Descriptor, OkId};
+//!
+//! let blob = b"hello world";
+//! let descriptor = Descriptor::from_blob("application/octet-stream", blob);
+//! assert_eq!(
+//!     descriptor.digest.to_oci_digest().unwrap(),
+//!     "sha256:b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+//! );
+//! descriptor.verify(&blob[..]).unwrap();
+//! assert!(descriptor.verify(&b"hello world!"[..]).is_err());
+//! ```
+
+use std::io::{self, Read};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::Digest as _;
+
+use crate::{BinaryType, Error, OkId};
+
+const SHA256: &str = "sha256";
+#[cfg(feature = "blake3")]
+const BLAKE3: &str = "blake3";
+
+impl OkId {
+    /// Format as an OCI `algorithm:hex` digest.
+    pub fn to_oci_digest(&self) -> Result<String, Error> {
+        let algorithm = match self.hash_type {
+            BinaryType::Sha256 => SHA256,
+            #[cfg(feature = "blake3")]
+            BinaryType::Blake3 => BLAKE3,
+            _ => return Err(Error::InvalidType),
+        };
+        Ok(format!(
+            "{}:{}",
+            algorithm,
+            hex::encode(self.digest_bytes())
+        ))
+    }
+
+    /// Parse an OCI digest such as `sha256:…`.
+    ///
+    /// Fails with [`Error::InvalidFormat`] for strings outside the OCI grammar, including
+    /// uppercase hex for registered algorithms, and [`Error::UnsupportedAlgorithm`] for
+    /// valid digests of algorithms without an OkId kind.
+    pub fn from_oci_digest(s: &str) -> Result<OkId, Error> {
+        let (algorithm, encoded) = s.split_once(':').ok_or(Error::InvalidFormat)?;
+        if !is_algorithm(algorithm) || !is_encoded(encoded) {
+            return Err(Error::InvalidFormat);
+        }
+        let hash_type = match algorithm {
+            SHA256 => BinaryType::Sha256,
+            #[cfg(feature = "blake3")]
+            BLAKE3 => BinaryType::Blake3,
+            _ => return Err(Error::UnsupportedAlgorithm(algorithm.to_string())),
+        };
+        if encoded.len() != 64
+            || !encoded
+                .bytes()
+                .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        {
+            return Err(Error::InvalidFormat);
+        }
+        OkId::from_digest_bytes(hash_type, &hex::decode(encoded)?)
+    }
+}
+
+/// `[a-z0-9]+([+._-][a-z0-9]+)*`
+fn is_algorithm(s: &str) -> bool {
+    s.split(['+', '.', '_', '-']).all(|component| {
+        !component.is_empty()
+            && component
+                .bytes()
+                .all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9'))
+    })
+}
+
+/// `[a-zA-Z0-9=_-]+`
+fn is_encoded(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'=' | b'_' | b'-'))
+}
+
+/// An OCI content descriptor.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Descriptor {
+    /// Media type of the referenced content
+    pub media_type: String,
+    /// Digest of the referenced content
+    #[serde(
+        serialize_with = "serialize_digest",
+        deserialize_with = "deserialize_digest"
+    )]
+    pub digest: OkId,
+    /// Size of the referenced content in bytes
+    pub size: u64,
+}
+
+impl Descriptor {
+    /// Create a descriptor.
+    pub fn new(media_type: impl Into<String>, digest: OkId, size: u64) -> Self {
+        Descriptor {
+            media_type: media_type.into(),
+            digest,
+            size,
+        }
+    }
+
+    /// Describe `blob` with a SHA-256 digest.
+    pub fn from_blob(media_type: impl Into<String>, blob: &[u8]) -> Self {
+        Descriptor::new(
+            media_type,
+            sha2::Sha256::new_with_prefix(blob).into(),
+            blob.len() as u64,
+        )
+    }
+
+    /// Wrap `reader` so that reading it to the end checks the content against this descriptor.
+    ///
+    /// Fails with [`Error::InvalidType`] if the digest is not an OCI algorithm.
+    pub fn verifying_reader<R: Read>(&self, reader: R) -> Result<VerifyingReader<R>, Error> {
+        let hasher = match self.digest.hash_type {
+            BinaryType::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+            #[cfg(feature = "blake3")]
+            BinaryType::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            _ => return Err(Error::InvalidType),
+        };
+        Ok(VerifyingReader {
+            inner: reader,
+            expected: self.digest,
+            size: self.size,
+            read: 0,
+            hasher: Some(hasher),
+        })
+    }
+
+    /// Read `reader` to the end and check it against this descriptor.
+    ///
+    /// A mismatch is an [`io::ErrorKind::InvalidData`] error wrapping an [`Error`].
+    pub fn verify(&self, reader: impl Read) -> io::Result<()> {
+        let mut reader = self
+            .verifying_reader(reader)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        io::copy(&mut reader, &mut io::sink())?;
+        Ok(())
+    }
+}
+
+fn serialize_digest<S: Serializer>(digest: &OkId, serializer: S) -> Result<S::Ok, S::Error> {
+    let digest = digest.to_oci_digest().map_err(serde::ser::Error::custom)?;
+    serializer.serialize_str(&digest)
+}
+
+fn deserialize_digest<'de, D: Deserializer<'de>>(deserializer: D) -> Result<OkId, D::Error> {
+    let digest = String::deserialize(deserializer)?;
+    OkId::from_oci_digest(&digest).map_err(serde::de::Error::custom)
+}
+
+enum Hasher {
+    Sha256(sha2::Sha256),
+    #[cfg(feature = "blake3")]
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(data),
+            #[cfg(feature = "blake3")]
+            Hasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    fn finalize(self) -> OkId {
+        match self {
+            Hasher::Sha256(hasher) => hasher.into(),
+            #[cfg(feature = "blake3")]
+            Hasher::Blake3(hasher) => (*hasher).into(),
+        }
+    }
+}
+
+/// A reader that checks the size and digest of the content passing through it.
+///
+/// Reads fail with [`io::ErrorKind::InvalidData`] as soon as the content is longer than
+/// expected, and at the end of the content if its size or digest don't match.
+pub struct VerifyingReader<R> {
+    inner: R,
+    expected: OkId,
+    size: u64,
+    read: u64,
+    hasher: Option<Hasher>,
+}
+
+impl<R> VerifyingReader<R> {
+    /// Unwrap the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for VerifyingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        let Some(hasher) = self.hasher.as_mut() else {
+            return Ok(n);
+        };
+        self.read += n as u64;
+        if self.read > self.size {
+            return Err(invalid_data(Error::InvalidLength));
+        }
+        if n > 0 {
+            hasher.update(&buf[..n]);
+            return Ok(n);
+        }
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.read != self.size {
+            return Err(invalid_data(Error::InvalidLength));
+        }
+        let actual = self.hasher.take().map(Hasher::finalize);
+        match actual {
+            Some(actual) if actual != self.expected => Err(invalid_data(Error::DigestMismatch {
+                expected: Box::new(self.expected),
+                actual: Box::new(actual),
+            })),
+            _ => Ok(0),
+        }
+    }
+}
+
+fn invalid_data(e: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
@@ -117,6 +117,18 @@ impl PartialEq for OkId {
             (Digest::Fingerprint(_), _) => false,
             (Digest::PubKey(a), Digest::PubKey(b)) => a == b,
             (Digest::PubKey(_), _) => false,
+            (Digest::Signature(a), Digest::Signature(b)) => a == b,
+            (Digest::Signature(_), _) => false,
+            (Digest::SecretKey(a), Digest::SecretKey(b)) => a == b,
+            (Digest::SecretKey(_), _) => false,
+            (Digest::X25519(a), Digest::X25519(b)) => a == b,
+            (Digest::X25519(_), _) => false,
+            (Digest::Secp256k1(a), Digest::Secp256k1(b)) => a == b,
+            (Digest::Secp256k1(_), _) => false,
+            (Digest::P256(a), Digest::P256(b)) => a == b,
+            (Digest::P256(_), _) => false,
+            (Digest::NixStore(a), Digest::NixStore(b)) => a == b,
+            (Digest::NixStore(_), _) => false,
         }
     }
 }
@@ -165,6 +177,30 @@ impl Hash for OkId {
                 state.write_u8(b'p');
                 d.0.hash(state);
             }
+            Digest::Signature(d) => {
+                state.write_u8(b's');
+                d.0.hash(state);
+            }
+            Digest::SecretKey(d) => {
+                state.write_u8(b'k');
+                d.0.hash(state);
+            }
+            Digest::X25519(d) => {
+                state.write_u8(b'x');
+                d.0.hash(state);
+            }
+            Digest::Secp256k1(d) => {
+                state.write_u8(b'e');
+                d.0.hash(state);
+            }
+            Digest::P256(d) => {
+                state.write_u8(b'r');
+                d.0.hash(state);
+            }
+            Digest::NixStore(d) => {
+                state.write_u8(b'n');
+                d.0.hash(state);
+            }
         }
     }
 }
@@ -196,6 +232,12 @@ impl Display for OkId {
             Digest::Uuid(uuid) => uuid.fmt(f),
             Digest::Fingerprint(fingerprint) => fingerprint.fmt(f),
             Digest::PubKey(pub_key) => Display::fmt(pub_key, f),
+            Digest::Signature(signature) => Display::fmt(signature, f),
+            Digest::SecretKey(secret_key) => Display::fmt(secret_key, f),
+            Digest::X25519(x25519) => Display::fmt(x25519, f),
+            Digest::Secp256k1(secp256k1) => Display::fmt(secp256k1, f),
+            Digest::P256(p256) => Display::fmt(p256, f),
+            Digest::NixStore(nix_store) => Display::fmt(nix_store, f),
         }
     }
 }
@@ -219,6 +261,12 @@ impl fmt::Debug for OkId {
             Digest::Uuid(uuid) => Display::fmt(uuid, f),
             Digest::Fingerprint(fingerprint) => Display::fmt(fingerprint, f),
             Digest::PubKey(pub_key) => Display::fmt(pub_key, f),
+            Digest::Signature(signature) => Display::fmt(signature, f),
+            Digest::SecretKey(secret_key) => fmt::Debug::fmt(secret_key, f),
+            Digest::X25519(x25519) => Display::fmt(x25519, f),
+            Digest::Secp256k1(secp256k1) => Display::fmt(secp256k1, f),
+            Digest::P256(p256) => Display::fmt(p256, f),
+            Digest::NixStore(nix_store) => Display::fmt(nix_store, f),
         }
     }
 }
@@ -265,6 +313,12 @@ impl OkId {
             BinaryType::Uuid => b'i',
             BinaryType::PubKey => b'p',
             BinaryType::Fingerprint => b'f',
+            BinaryType::Signature => b's',
+            BinaryType::SecretKey => b'k',
+            BinaryType::X25519 => b'x',
+            BinaryType::Secp256k1 => b'e',
+            BinaryType::P256 => b'r',
+            BinaryType::NixStore => b'n',
         };
         match self.digest {
             #[cfg(feature = "sha1")]
@@ -330,6 +384,54 @@ impl OkId {
                     i += 1;
                 }
             }
+            Digest::Signature(signature) => {
+                let signature_bytes = signature.0;
+                let mut i = 0;
+                while i < signature_bytes.len() {
+                    bytes[i + 1] = signature_bytes[i];
+                    i += 1;
+                }
+            }
+            Digest::SecretKey(secret_key) => {
+                let secret_key_bytes = secret_key.0;
+                let mut i = 0;
+                while i < secret_key_bytes.len() {
+                    bytes[i + 1] = secret_key_bytes[i];
+                    i += 1;
+                }
+            }
+            Digest::X25519(x25519) => {
+                let x25519_bytes = x25519.0;
+                let mut i = 0;
+                while i < x25519_bytes.len() {
+                    bytes[i + 1] = x25519_bytes[i];
+                    i += 1;
+                }
+            }
+            Digest::Secp256k1(secp256k1) => {
+                let secp256k1_bytes = secp256k1.0;
+                let mut i = 0;
+                while i < secp256k1_bytes.len() {
+                    bytes[i + 1] = secp256k1_bytes[i];
+                    i += 1;
+                }
+            }
+            Digest::P256(p256) => {
+                let p256_bytes = p256.0;
+                let mut i = 0;
+                while i < p256_bytes.len() {
+                    bytes[i + 1] = p256_bytes[i];
+                    i += 1;
+                }
+            }
+            Digest::NixStore(nix_store) => {
+                let nix_store_bytes = nix_store.0;
+                let mut i = 0;
+                while i < nix_store_bytes.len() {
+                    bytes[i + 1] = nix_store_bytes[i];
+                    i += 1;
+                }
+            }
             Digest::Fingerprint(fingerprint) => {
                 let fingerprint_bytes = fingerprint.0.to_bytes();
                 let mut i = 0;
@@ -343,7 +445,7 @@ impl OkId {
     }
 }
 
-/// Create a path-safe string from an OkId
+/// Create a path-safe string from an OkId, parsed back by [`OkId::from_ascii`]
 /// ```
 /// let okid = okid::okid!("bːfcca4276240cd3aa68d8fbb4917e8392c1166a3fcbf7c186b05e4599f38d391a");
 /// let path_safe = okid::to_ascii(okid);
@@ -351,7 +453,7 @@ impl OkId {
 /// assert_eq!(path_safe, "1/b/fcca4276240cd3aa68d8fbb4917e8392c1166a3fcbf7c186b05e4599f38d391a");
 /// ```
 pub fn to_ascii(id: OkId) -> String {
-    format!("1/{}/{}", id.hash_type.char_code(), id.digest)
+    crate::to_ascii_sharded(id, crate::Sharding::default())
 }
 
 #[cfg_attr(wasm_bindgen, wasm_bindgen)]
@@ -375,7 +477,9 @@ impl TryFrom<&url::Url> for OkId {
     /// Parse an OkId from URL path segments.
     ///
     /// This will iterate through all path segments and return the first valid OkId found.
-    /// URL-encoded separators (e.g., `%CB%90`) are automatically decoded.
+    /// URL-encoded separators (e.g., `%CB%90`) are automatically decoded. With the `ni` feature,
+    /// `ni:` and `nih:` URLs are parsed as RFC 6920 names instead. See [`crate::url`] for query
+    /// and fragment lookup.
     ///
     /// # Example
     /// ```
@@ -386,17 +490,35 @@ impl TryFrom<&url::Url> for OkId {
     /// let okid = OkId::try_from(&url).unwrap();
     /// ```
     fn try_from(url: &url::Url) -> Result<Self, Self::Error> {
-        if let Some(segments) = url.path_segments() {
-            for segment in segments {
-                // URL path segments are percent-encoded, so we need to decode them
-                let decoded = percent_encoding::percent_decode_str(segment).decode_utf8_lossy();
-                if let Ok(okid) = parse_okid(&decoded) {
-                    return Ok(okid);
-                }
-            }
+        #[cfg(feature = "ni")]
+        if matches!(url.scheme(), "ni" | "nih") {
+            return OkId::from_ni_uri(url.as_str());
+        }
+        match url.path_segments() {
+            Some(segments) => from_path_segments(segments).map_err(|_| crate::Error::NotFound),
+            None => Err(crate::Error::NotFound),
+        }
+    }
+}
+
+/// The first OkId among percent-encoded path segments.
+///
+/// If none parses, the error is that of the last segment containing a separator, so callers
+/// can report why an id-looking segment was rejected, or [`crate::Error::NotFound`].
+pub(crate) fn from_path_segments<'a>(
+    segments: impl IntoIterator<Item = &'a str>,
+) -> Result<OkId, crate::Error> {
+    let mut error = crate::Error::NotFound;
+    for segment in segments {
+        // URL path segments are percent-encoded, so we need to decode them
+        let decoded = percent_encoding::percent_decode_str(segment).decode_utf8_lossy();
+        match parse_okid(&decoded) {
+            Ok(okid) => return Ok(okid),
+            Err(e) if decoded.contains(SEPARATOR) => error = e,
+            Err(_) => {}
         }
-        Err(crate::Error::NotFound)
     }
+    Err(error)
 }
 
 impl TryFrom<url::Url> for OkId {
@@ -0,0 +1,103 @@
+#[cfg(feature = "p256")]
+use crate::{BinaryType, Digest, OkId};
+use {
+    crate::{hex_to_byte, Error},
+    std::{fmt::Display, str::FromStr},
+    zerocopy::{ByteEq, ByteHash, FromBytes, Immutable, IntoBytes, Unaligned},
+};
+
+pub(crate) const P256_LEN: usize = 33;
+
+#[derive(Copy, Clone, Debug, ByteEq, Immutable, IntoBytes, ByteHash, FromBytes, Unaligned)]
+#[repr(C)]
+pub(super) struct P256(pub(crate) [u8; P256_LEN]);
+
+#[cfg(feature = "p256")]
+impl From<&p256::PublicKey> for OkId {
+    fn from(value: &p256::PublicKey) -> Self {
+        use p256::elliptic_curve::sec1::ToEncodedPoint;
+        let point = value.to_encoded_point(true);
+        let mut buf = [0; P256_LEN];
+        buf.copy_from_slice(point.as_bytes());
+        Self {
+            hash_type: BinaryType::P256,
+            digest: Digest::P256(P256(buf)),
+        }
+    }
+}
+
+#[cfg(feature = "p256")]
+impl crate::IntoOkId for &p256::PublicKey {}
+
+#[cfg(feature = "p256")]
+impl From<&p256::ecdsa::VerifyingKey> for OkId {
+    fn from(value: &p256::ecdsa::VerifyingKey) -> Self {
+        OkId::from(&p256::PublicKey::from(value))
+    }
+}
+
+#[cfg(feature = "p256")]
+impl crate::IntoOkId for &p256::ecdsa::VerifyingKey {}
+
+#[cfg(feature = "p256")]
+impl TryFrom<OkId> for p256::PublicKey {
+    type Error = Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        match value.digest {
+            Digest::P256(key) => {
+                p256::PublicKey::from_sec1_bytes(&key.0).map_err(|_| Error::InvalidFormat)
+            }
+            _ => Err(Error::InvalidType),
+        }
+    }
+}
+
+#[cfg(feature = "p256")]
+impl TryFrom<OkId> for p256::ecdsa::VerifyingKey {
+    type Error = Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        p256::PublicKey::try_from(value).map(Into::into)
+    }
+}
+
+impl Display for P256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let buf = &hex::encode(self.0);
+        f.write_str(buf)?;
+        Ok(())
+    }
+}
+
+impl FromStr for P256 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let buf = hex::decode(s)?;
+        if buf.len() != P256_LEN {
+            return Err(Error::InvalidLength);
+        }
+        let mut key = [0; P256_LEN];
+        key.copy_from_slice(&buf);
+        Ok(P256(key))
+    }
+}
+
+pub(crate) const fn parse_p256_bytes(bytes: &[u8], start: usize) -> Option<P256> {
+    let mut result = [0u8; P256_LEN];
+    let mut i = 0;
+    while i < P256_LEN * 2 {
+        let high = match hex_to_byte(bytes[start + i]) {
+            Some(b) => b,
+            None => return None,
+        };
+        let low = match hex_to_byte(bytes[start + i + 1]) {
+            Some(b) => b,
+            None => return None,
+        };
+        result[i / 2] = (high << 4) | low;
+        i += 2;
+    }
+    Some(P256(result))
+}
@@ -3,6 +3,7 @@ use crate::binary_type::BinaryType;
 use crate::blake3;
 use crate::digest::Digest;
 use crate::error::Error;
+#[allow(deprecated)]
 #[cfg(feature = "sha1")]
 use crate::sha1;
 #[cfg(feature = "sha2")]
@@ -13,9 +14,19 @@ use crate::sha3;
 use crate::ulid;
 #[cfg(feature = "uuid")]
 use crate::uuid;
-use crate::{fingerprint, pub_key, OkId, SEPARATOR, SEPARATOR_BYTES, SEPARATOR_BYTES_LEN};
+use crate::{
+    fingerprint, nix_store, p256, pub_key, secp256k1, secret_key, signature, x25519, OkId,
+    SEPARATOR, SEPARATOR_BYTES, SEPARATOR_BYTES_LEN,
+};
 
 pub(crate) fn parse_okid(s: &str) -> Result<OkId, Error> {
+    #[cfg(feature = "pkarr")]
+    if let Some(id) = (!s.contains(SEPARATOR))
+        .then(|| OkId::from_z32(s).ok())
+        .flatten()
+    {
+        return Ok(id);
+    }
     let mut chars = s.chars();
     let hash_type: BinaryType = chars.next().ok_or(Error::InvalidFormat)?.into();
     if chars.next() != Some(SEPARATOR) {
@@ -62,6 +73,30 @@ pub(crate) fn parse_okid(s: &str) -> Result<OkId, Error> {
             hash_type,
             digest: Digest::PubKey(rest.parse()?),
         }),
+        BinaryType::Signature => Ok(OkId {
+            hash_type,
+            digest: Digest::Signature(rest.parse()?),
+        }),
+        BinaryType::SecretKey => Ok(OkId {
+            hash_type,
+            digest: Digest::SecretKey(rest.parse()?),
+        }),
+        BinaryType::X25519 => Ok(OkId {
+            hash_type,
+            digest: Digest::X25519(rest.parse()?),
+        }),
+        BinaryType::Secp256k1 => Ok(OkId {
+            hash_type,
+            digest: Digest::Secp256k1(rest.parse()?),
+        }),
+        BinaryType::P256 => Ok(OkId {
+            hash_type,
+            digest: Digest::P256(rest.parse()?),
+        }),
+        BinaryType::NixStore => Ok(OkId {
+            hash_type,
+            digest: Digest::NixStore(rest.parse()?),
+        }),
     }
 }
 
@@ -92,6 +127,12 @@ const fn parse_okid_bytes(bytes: &[u8]) -> Option<OkId> {
         b'i' => BinaryType::Uuid,
         b'f' => BinaryType::Fingerprint,
         b'p' => BinaryType::PubKey,
+        b's' => BinaryType::Signature,
+        b'k' => BinaryType::SecretKey,
+        b'x' => BinaryType::X25519,
+        b'e' => BinaryType::Secp256k1,
+        b'r' => BinaryType::P256,
+        b'n' => BinaryType::NixStore,
         _ => return None,
     };
 
@@ -209,6 +250,78 @@ const fn parse_okid_bytes(bytes: &[u8]) -> Option<OkId> {
                 None => None,
             }
         }
+        BinaryType::Signature => {
+            if bytes.len() != content_start + 128 {
+                return None;
+            }
+            match signature::parse_signature_bytes(bytes, content_start) {
+                Some(digest) => Some(OkId {
+                    hash_type,
+                    digest: Digest::Signature(digest),
+                }),
+                None => None,
+            }
+        }
+        BinaryType::SecretKey => {
+            if bytes.len() != content_start + 64 {
+                return None;
+            }
+            match secret_key::parse_secret_key_bytes(bytes, content_start) {
+                Some(digest) => Some(OkId {
+                    hash_type,
+                    digest: Digest::SecretKey(digest),
+                }),
+                None => None,
+            }
+        }
+        BinaryType::X25519 => {
+            if bytes.len() != content_start + 64 {
+                return None;
+            }
+            match x25519::parse_x25519_bytes(bytes, content_start) {
+                Some(digest) => Some(OkId {
+                    hash_type,
+                    digest: Digest::X25519(digest),
+                }),
+                None => None,
+            }
+        }
+        BinaryType::Secp256k1 => {
+            if bytes.len() != content_start + 66 {
+                return None;
+            }
+            match secp256k1::parse_secp256k1_bytes(bytes, content_start) {
+                Some(digest) => Some(OkId {
+                    hash_type,
+                    digest: Digest::Secp256k1(digest),
+                }),
+                None => None,
+            }
+        }
+        BinaryType::P256 => {
+            if bytes.len() != content_start + 66 {
+                return None;
+            }
+            match p256::parse_p256_bytes(bytes, content_start) {
+                Some(digest) => Some(OkId {
+                    hash_type,
+                    digest: Digest::P256(digest),
+                }),
+                None => None,
+            }
+        }
+        BinaryType::NixStore => {
+            if bytes.len() != content_start + 40 {
+                return None;
+            }
+            match nix_store::parse_nix_store_bytes(bytes, content_start) {
+                Some(digest) => Some(OkId {
+                    hash_type,
+                    digest: Digest::NixStore(digest),
+                }),
+                None => None,
+            }
+        }
         _ => None,
     }
 }
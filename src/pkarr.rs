@@ -0,0 +1,132 @@
+//! pkarr public keys and signed packets pointing at content OkIds.
+//!
+//! A pointer is a TXT record `okid=<okid>` under a name relative to the publisher's key,
+//! e.g. `latest.<z32 key>`. Building and verifying packets happens offline; publishing them
+//! is left to a pkarr client.
+//!
+//! # Examples
+//! ```
+//! use okid::OkId;
+//!
+//! let secret = OkId::from(&ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]));
+//! let keypair = pkarr::Keypair::try_from(secret).unwrap();
+//! let publisher = OkId::from(keypair.public_key());
+//! let content: OkId = "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+//!     .parse()
+//!     .unwrap();
+//!
+//! let packet = okid::pkarr::signed_packet(&keypair, [("latest", content)], 300).unwrap();
+//! let records =
+//!     okid::pkarr::verify_signed_packet(&publisher, &packet.to_relay_payload()).unwrap();
+//! assert_eq!(records["latest"], content);
+//! assert_eq!(publisher.to_z32().unwrap().parse::<OkId>().unwrap(), publisher);
+//! ```
+
+use std::collections::BTreeMap;
+
+use pkarr::{dns::rdata::RData, Keypair, PublicKey, SignedPacket};
+
+use crate::{Digest, Error, OkId};
+
+/// TXT attribute holding the OkId a name points at.
+pub const TXT_KEY: &str = "okid";
+
+const Z32_LEN: usize = 52;
+const URI_PREFIX: &str = "pk:";
+const APEX: &str = "@";
+
+impl OkId {
+    /// Format this public key in the z-base-32 form pkarr uses for its domains.
+    pub fn to_z32(&self) -> Result<String, Error> {
+        Ok(PublicKey::try_from(*self)?.to_z32())
+    }
+
+    /// Parse a z-base-32 public key, optionally prefixed with `pk:`.
+    pub fn from_z32(s: &str) -> Result<OkId, Error> {
+        let s = s.strip_prefix(URI_PREFIX).unwrap_or(s);
+        if s.len() != Z32_LEN {
+            return Err(Error::InvalidLength);
+        }
+        PublicKey::try_from(s)
+            .map(OkId::from)
+            .map_err(|_| Error::InvalidFormat)
+    }
+}
+
+impl TryFrom<OkId> for Keypair {
+    type Error = Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        match value.digest {
+            Digest::SecretKey(secret_key) => Ok(Keypair::from_secret_key(&secret_key.0)),
+            _ => Err(Error::InvalidType),
+        }
+    }
+}
+
+/// Build a packet signed by `keypair` with one `okid=` TXT record per name.
+///
+/// Names are relative to the publisher's key; `@` is the key itself.
+pub fn signed_packet<'a>(
+    keypair: &Keypair,
+    records: impl IntoIterator<Item = (&'a str, OkId)>,
+    ttl: u32,
+) -> Result<SignedPacket, Error> {
+    let values = records
+        .into_iter()
+        .map(|(name, id)| (name, format!("{}={}", TXT_KEY, id)))
+        .collect::<Vec<_>>();
+    values
+        .iter()
+        .try_fold(SignedPacket::builder(), |builder, (name, value)| {
+            let name = match *name {
+                APEX => ".",
+                name => name,
+            };
+            let name = name.try_into().map_err(|_| Error::InvalidFormat)?;
+            let txt = value
+                .as_str()
+                .try_into()
+                .map_err(|_| Error::InvalidFormat)?;
+            Ok::<_, Error>(builder.txt(name, txt, ttl))
+        })?
+        .sign(keypair)
+        .map_err(|e| Error::InvalidSignature(e.to_string()))
+}
+
+/// The `okid=` TXT records of a packet, keyed by name relative to the publisher's key.
+///
+/// Records at the key itself are keyed `@`. Other records are ignored.
+pub fn records(packet: &SignedPacket) -> BTreeMap<String, OkId> {
+    let origin = packet.public_key().to_z32();
+    packet
+        .all_resource_records()
+        .filter_map(|rr| {
+            let RData::TXT(txt) = &rr.rdata else {
+                return None;
+            };
+            let id = txt.attributes().remove(TXT_KEY)??.parse().ok()?;
+            let name = rr.name.to_string();
+            let name = match name.strip_suffix(origin.as_str()) {
+                Some("") => APEX.to_string(),
+                Some(prefix) => prefix.trim_end_matches('.').to_string(),
+                None => name,
+            };
+            Some((name, id))
+        })
+        .collect()
+}
+
+/// Verify a packet as served by pkarr relays and return its `okid=` records.
+///
+/// `payload` is the packet without the leading public key; the signature is checked
+/// against `publisher`.
+pub fn verify_signed_packet(
+    publisher: &OkId,
+    payload: &[u8],
+) -> Result<BTreeMap<String, OkId>, Error> {
+    let public_key = PublicKey::try_from(*publisher)?;
+    let packet = SignedPacket::from_relay_payload(&public_key, &payload.to_vec().into())
+        .map_err(|e| Error::InvalidSignature(e.to_string()))?;
+    Ok(records(&packet))
+}
@@ -23,6 +23,19 @@ impl From<pkarr::PublicKey> for OkId {
     }
 }
 
+#[cfg(feature = "pkarr")]
+impl TryFrom<OkId> for pkarr::PublicKey {
+    type Error = super::Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        match value.digest {
+            super::Digest::PubKey(pub_key) => pkarr::PublicKey::try_from(&pub_key.0)
+                .map_err(|e| super::Error::InvalidSignature(e.to_string())),
+            _ => Err(super::Error::InvalidType),
+        }
+    }
+}
+
 #[cfg(feature = "pkarr")]
 impl super::IntoOkId for pkarr::PublicKey {}
 
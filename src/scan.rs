@@ -0,0 +1,158 @@
+//! Finding OkIds in free-form text such as logs, chat messages or markdown.
+//!
+//! An id is found where a type code follows a word boundary, then comes the separator `ː`
+//! and a run of hex digits that ends at a word boundary. Candidates that do not parse, for
+//! instance because the digest has the wrong length, are skipped.
+
+use std::{
+    fmt,
+    io::{self, BufRead},
+    ops::Range,
+};
+
+use crate::{parse::parse_okid, OkId, SEPARATOR_BYTES, SEPARATOR_BYTES_LEN};
+
+/// Every valid OkId in `text` with its byte range.
+///
+/// ```
+/// let text = "fetched `2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9`, \
+///             ignored xfː0011223344556677 and fː00112233";
+/// let found: Vec<_> = okid::scan(text).collect();
+/// assert_eq!(found.len(), 1);
+/// let (range, id) = &found[0];
+/// assert_eq!(&text[range.clone()], id.to_string());
+/// ```
+pub fn scan(text: &str) -> impl Iterator<Item = (Range<usize>, OkId)> + '_ {
+    scan_bytes(text.as_bytes())
+}
+
+/// Every valid OkId in bytes that may not be UTF-8, with its byte range.
+fn scan_bytes(bytes: &[u8]) -> impl Iterator<Item = (Range<usize>, OkId)> + '_ {
+    let mut position = 0;
+    std::iter::from_fn(move || {
+        while let Some(found) = bytes[position..]
+            .windows(SEPARATOR_BYTES_LEN)
+            .position(|window| window == SEPARATOR_BYTES)
+        {
+            let separator = position + found;
+            position = separator + SEPARATOR_BYTES_LEN;
+            let Some(start) = separator.checked_sub(1) else {
+                continue;
+            };
+            if !bytes[start].is_ascii_alphanumeric()
+                || char_before(bytes, start).is_some_and(is_word)
+            {
+                continue;
+            }
+            let end = position
+                + bytes[position..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_hexdigit())
+                    .count();
+            if char_at(bytes, end).is_some_and(is_word) {
+                continue;
+            }
+            let candidate = std::str::from_utf8(&bytes[start..end]);
+            if let Some(id) = candidate.ok().and_then(|s| parse_okid(s).ok()) {
+                position = end;
+                return Some((start..end, id));
+            }
+        }
+        position = bytes.len();
+        None
+    })
+}
+
+/// Streams every valid OkId from a reader, with byte ranges from the start of the stream.
+///
+/// Ids never contain line breaks, so the reader is scanned a line at a time and the input
+/// need not be UTF-8.
+pub fn scan_reader<R: BufRead>(reader: R) -> ScanReader<R> {
+    ScanReader::new(reader, Box::new(|line| scan_bytes(line).collect()))
+}
+
+/// Finds the ids in one line of a stream.
+type LineScanner = Box<dyn Fn(&[u8]) -> Vec<(Range<usize>, OkId)> + Send + Sync>;
+
+/// The iterator of [`scan_reader`] and [`scan_secrets_reader`](crate::scan_secrets_reader).
+pub struct ScanReader<R> {
+    reader: R,
+    scan: LineScanner,
+    line: Vec<u8>,
+    offset: u64,
+    // The ids of the current line, in reverse
+    found: Vec<(Range<u64>, OkId)>,
+}
+
+impl<R> ScanReader<R> {
+    /// Scan each line of `reader` with `scan`.
+    pub(crate) fn new(reader: R, scan: LineScanner) -> Self {
+        ScanReader {
+            reader,
+            scan,
+            line: Vec::new(),
+            offset: 0,
+            found: Vec::new(),
+        }
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for ScanReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScanReader")
+            .field("reader", &self.reader)
+            .field("offset", &self.offset)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: BufRead> Iterator for ScanReader<R> {
+    type Item = io::Result<(Range<u64>, OkId)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.found.is_empty() {
+            self.line.clear();
+            match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(0) => return None,
+                Ok(read) => {
+                    let offset = self.offset;
+                    self.found
+                        .extend((self.scan)(&self.line).into_iter().map(|(range, id)| {
+                            (offset + range.start as u64..offset + range.end as u64, id)
+                        }));
+                    self.found.reverse();
+                    self.offset += read as u64;
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        self.found.pop().map(Ok)
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The character starting at byte `i`, if it is valid UTF-8.
+fn char_at(bytes: &[u8], i: usize) -> Option<char> {
+    let len = match *bytes.get(i)? {
+        b if b < 0x80 => 1,
+        b if b >= 0xf0 => 4,
+        b if b >= 0xe0 => 3,
+        _ => 2,
+    };
+    std::str::from_utf8(bytes.get(i..i + len)?)
+        .ok()?
+        .chars()
+        .next()
+}
+
+/// The character ending just before byte `i`, if it is valid UTF-8.
+fn char_before(bytes: &[u8], i: usize) -> Option<char> {
+    (1..=i.min(4))
+        .map(|n| i - n)
+        .find(|&j| bytes[j] & 0xc0 != 0x80)
+        .and_then(|j| char_at(bytes, j))
+}
@@ -0,0 +1,201 @@
+//! Sealed display-safe secrets: the wireformat bytes are encrypted with XChaCha20-Poly1305
+//! before they are hidden by a [`DisplaySafeCarrier`].
+//!
+//! Anyone can still see that a sealed secret is there and which key sealed it, but only
+//! holders of the key can read or forge the id. The sealed bytes are a version byte, the
+//! 32-bit key id, a random 24-byte nonce and the ciphertext, with the version and key id
+//! authenticated as associated data. Pass every current key to [`OkId::unseal`] to rotate
+//! keys without breaking old secrets.
+//!
+//! # Examples
+//! ```
+//! use okid::{seal::SealKey, OkId};
+//!
+//! let id: OkId = "fː0123456789abcdef".parse().unwrap();
+//! let old = SealKey::generate(1).unwrap();
+//! let new = SealKey::generate(2).unwrap();
+//! let sealed = id.seal(&old).unwrap();
+//! assert_eq!(OkId::from_display_safe(&sealed), None);
+//! assert_eq!(okid::seal::key_id(&sealed), Some(1));
+//! assert_eq!(OkId::unseal(&[new, old], &sealed).unwrap(), id);
+//! ```
+
+use std::fmt;
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use jetstream_wireformat::WireFormat;
+#[cfg(wasm_bindgen)]
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    secret::{DisplaySafeCarrier, VariationSelectors},
+    Error, OkId,
+};
+
+const VERSION: u8 = 0;
+const KEY_ID_LEN: usize = 4;
+const NONCE_LEN: usize = 24;
+const HEADER_LEN: usize = 1 + KEY_ID_LEN;
+
+/// A 256-bit key for sealing secrets, with the id that is stored alongside them.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SealKey {
+    id: u32,
+    key: [u8; 32],
+}
+
+impl fmt::Debug for SealKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SealKey")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SealKey {
+    /// Create a key from raw bytes.
+    pub const fn from_bytes(id: u32, key: [u8; 32]) -> Self {
+        SealKey { id, key }
+    }
+
+    /// Generate a new random key.
+    pub fn generate(id: u32) -> Result<Self, Error> {
+        let mut key = [0u8; 32];
+        getrandom::fill(&mut key)?;
+        Ok(SealKey { id, key })
+    }
+
+    /// The key id.
+    pub const fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The raw key bytes.
+    pub const fn as_bytes(&self) -> &[u8; 32] {
+        &self.key
+    }
+}
+
+/// The id of the key that sealed `s`, without unsealing it.
+pub fn key_id(s: &str) -> Option<u32> {
+    key_id_with(&VariationSelectors::default(), s)
+}
+
+/// The id of the key that sealed `s` with `carrier`, without unsealing it.
+pub fn key_id_with(carrier: &(impl DisplaySafeCarrier + ?Sized), s: &str) -> Option<u32> {
+    let bytes = decode(carrier, s)?;
+    (bytes.len() > HEADER_LEN + NONCE_LEN).then(|| key_id_of(&bytes))
+}
+
+fn key_id_of(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes(bytes[1..HEADER_LEN].try_into().unwrap())
+}
+
+/// The carried bytes of `s`, which must be a whole sealed secret.
+fn decode(carrier: &(impl DisplaySafeCarrier + ?Sized), s: &str) -> Option<Vec<u8>> {
+    let (end, bytes) = carrier.decode_at(s, 0)?;
+    (end == s.len() && bytes.first() == Some(&VERSION)).then_some(bytes)
+}
+
+impl OkId {
+    /// Encrypt and embed an OkId like [`OkId::to_display_safe`].
+    pub fn seal(&self, key: &SealKey) -> Result<String, Error> {
+        self.seal_with(key, &VariationSelectors::default())
+    }
+
+    /// Encrypt and embed an OkId with a [`DisplaySafeCarrier`].
+    pub fn seal_with(
+        &self,
+        key: &SealKey,
+        carrier: &(impl DisplaySafeCarrier + ?Sized),
+    ) -> Result<String, Error> {
+        let mut plaintext = vec![];
+        self.encode(&mut plaintext).unwrap();
+        let mut nonce = [0u8; NONCE_LEN];
+        getrandom::fill(&mut nonce)?;
+
+        let mut bytes = vec![VERSION];
+        bytes.extend(key.id.to_be_bytes());
+        let ciphertext = XChaCha20Poly1305::new(&key.key.into())
+            .encrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: &bytes,
+                },
+            )
+            .map_err(|_| Error::InvalidLength)?;
+        bytes.extend(nonce);
+        bytes.extend(ciphertext);
+        Ok(carrier.encode(&bytes))
+    }
+
+    /// Decrypt a secret made by [`OkId::seal`] with whichever of `keys` has its key id.
+    ///
+    /// Fails with [`Error::InvalidFormat`] if `s` is not a sealed secret,
+    /// [`Error::UnknownKey`] if no key has its id, and [`Error::InvalidSignature`] if it
+    /// does not authenticate.
+    pub fn unseal(keys: &[SealKey], s: &str) -> Result<OkId, Error> {
+        OkId::unseal_with(keys, &VariationSelectors::default(), s)
+    }
+
+    /// Decrypt a secret made by [`OkId::seal_with`].
+    pub fn unseal_with(
+        keys: &[SealKey],
+        carrier: &(impl DisplaySafeCarrier + ?Sized),
+        s: &str,
+    ) -> Result<OkId, Error> {
+        let bytes = decode(carrier, s).ok_or(Error::InvalidFormat)?;
+        if bytes.len() <= HEADER_LEN + NONCE_LEN {
+            return Err(Error::InvalidFormat);
+        }
+        let (header, rest) = bytes.split_at(HEADER_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let id = key_id_of(&bytes);
+        let key = keys
+            .iter()
+            .find(|key| key.id == id)
+            .ok_or(Error::UnknownKey(id))?;
+        let plaintext = XChaCha20Poly1305::new(&key.key.into())
+            .decrypt(
+                &XNonce::from(<[u8; NONCE_LEN]>::try_from(nonce).unwrap()),
+                Payload {
+                    msg: ciphertext,
+                    aad: header,
+                },
+            )
+            .map_err(|_| Error::InvalidSignature("sealed secret failed to authenticate".into()))?;
+        let mut rest = plaintext.as_slice();
+        let okid: OkId = WireFormat::decode(&mut rest).map_err(|_| Error::InvalidFormat)?;
+        if !rest.is_empty() {
+            return Err(Error::InvalidLength);
+        }
+        Ok(okid)
+    }
+}
+
+#[cfg(wasm_bindgen)]
+fn js_key(key_id: u32, key: &[u8]) -> Result<SealKey, JsError> {
+    let key = key.try_into().map_err(|_| JsError::new("Invalid length"))?;
+    Ok(SealKey::from_bytes(key_id, key))
+}
+
+#[cfg(wasm_bindgen)]
+#[wasm_bindgen]
+impl OkId {
+    /// Encrypt and embed an OkId with a 32-byte key
+    #[wasm_bindgen(js_name = seal)]
+    pub fn js_seal(&self, key_id: u32, key: &[u8]) -> Result<String, JsError> {
+        self.seal(&js_key(key_id, key)?)
+            .map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Decrypt a sealed secret with a 32-byte key
+    #[wasm_bindgen(js_name = unseal)]
+    pub fn js_unseal(key_id: u32, key: &[u8], s: &str) -> Result<OkId, JsError> {
+        OkId::unseal(&[js_key(key_id, key)?], s).map_err(|e| JsError::new(&e.to_string()))
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(feature = "secp256k1")]
+use crate::{BinaryType, Digest, OkId};
+use {
+    crate::{hex_to_byte, Error},
+    std::{fmt::Display, str::FromStr},
+    zerocopy::{ByteEq, ByteHash, FromBytes, Immutable, IntoBytes, Unaligned},
+};
+
+pub(crate) const SECP256K1_LEN: usize = 33;
+
+#[derive(Copy, Clone, Debug, ByteEq, Immutable, IntoBytes, ByteHash, FromBytes, Unaligned)]
+#[repr(C)]
+pub(super) struct Secp256k1(pub(crate) [u8; SECP256K1_LEN]);
+
+#[cfg(feature = "secp256k1")]
+impl From<&k256::PublicKey> for OkId {
+    fn from(value: &k256::PublicKey) -> Self {
+        use k256::elliptic_curve::sec1::ToEncodedPoint;
+        let point = value.to_encoded_point(true);
+        let mut buf = [0; SECP256K1_LEN];
+        buf.copy_from_slice(point.as_bytes());
+        Self {
+            hash_type: BinaryType::Secp256k1,
+            digest: Digest::Secp256k1(Secp256k1(buf)),
+        }
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+impl crate::IntoOkId for &k256::PublicKey {}
+
+#[cfg(feature = "secp256k1")]
+impl From<&k256::ecdsa::VerifyingKey> for OkId {
+    fn from(value: &k256::ecdsa::VerifyingKey) -> Self {
+        OkId::from(&k256::PublicKey::from(value))
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+impl crate::IntoOkId for &k256::ecdsa::VerifyingKey {}
+
+#[cfg(feature = "secp256k1")]
+impl TryFrom<OkId> for k256::PublicKey {
+    type Error = Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        match value.digest {
+            Digest::Secp256k1(key) => {
+                k256::PublicKey::from_sec1_bytes(&key.0).map_err(|_| Error::InvalidFormat)
+            }
+            _ => Err(Error::InvalidType),
+        }
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+impl TryFrom<OkId> for k256::ecdsa::VerifyingKey {
+    type Error = Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        k256::PublicKey::try_from(value).map(Into::into)
+    }
+}
+
+impl Display for Secp256k1 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let buf = &hex::encode(self.0);
+        f.write_str(buf)?;
+        Ok(())
+    }
+}
+
+impl FromStr for Secp256k1 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let buf = hex::decode(s)?;
+        if buf.len() != SECP256K1_LEN {
+            return Err(Error::InvalidLength);
+        }
+        let mut key = [0; SECP256K1_LEN];
+        key.copy_from_slice(&buf);
+        Ok(Secp256k1(key))
+    }
+}
+
+pub(crate) const fn parse_secp256k1_bytes(bytes: &[u8], start: usize) -> Option<Secp256k1> {
+    let mut result = [0u8; SECP256K1_LEN];
+    let mut i = 0;
+    while i < SECP256K1_LEN * 2 {
+        let high = match hex_to_byte(bytes[start + i]) {
+            Some(b) => b,
+            None => return None,
+        };
+        let low = match hex_to_byte(bytes[start + i + 1]) {
+            Some(b) => b,
+            None => return None,
+        };
+        result[i / 2] = (high << 4) | low;
+        i += 2;
+    }
+    Some(Secp256k1(result))
+}
@@ -1,3 +1,10 @@
+//! Display-safe secrets: OkIds hidden in invisible characters after a visible emoji.
+//!
+//! A [`DisplaySafeCarrier`] decides how the wireformat bytes are hidden. The default,
+//! [`VariationSelectors`], puts one variation selector per byte after 🔒. [`Tags`] uses
+//! zero-width tag characters instead, for platforms that strip variation selectors. Both
+//! survive NFC and NFKC normalisation.
+//!
 //! # Examples
 //! ```
 //! use okid::OkId;
@@ -13,12 +20,25 @@
 //!     // 🔒
 //! }
 //! ```
+//!
+//! ```
+//! use okid::{secret::Tags, OkId};
+//!
+//! let id: OkId = "fː0123456789abcdef".parse().unwrap();
+//! let carrier = Tags::new('🔑').unwrap();
+//! let encoded = id.to_display_safe_with(&carrier);
+//! assert!(encoded.starts_with('🔑'));
+//! assert_eq!(OkId::from_display_safe_with(&carrier, &encoded), Some(id));
+//! ```
+
+use std::{borrow::Cow, io::BufRead, ops::Range};
 
 use jetstream_wireformat::WireFormat;
+use unicode_normalization::UnicodeNormalization;
 #[cfg(wasm_bindgen)]
 use wasm_bindgen::prelude::*;
 
-use crate::OkId;
+use crate::{Error, OkId, ScanReader};
 
 const SECRET_EMOJI: char = '🔒';
 const VS16: char = '\u{FE0F}'; // Emoji presentation selector
@@ -27,31 +47,211 @@ const VS_BASE_E01: u32 = 0xE0100;
 const VS_RANGE_FE_MAX: u32 = 0xFE0F;
 const VS_RANGE_E01_MAX: u32 = 0xE01EF;
 const FE_RANGE_SIZE: u8 = 16;
+const TAG_DIGIT_ZERO: u32 = 0xE0030;
+const TAG_SMALL_A: u32 = 0xE0061;
+const CANCEL_TAG: char = '\u{E007F}';
 
-#[cfg_attr(wasm_bindgen, wasm_bindgen)]
-impl OkId {
-    /// Embed an OkId inside a "secret" emoji using variation selectors
-    #[cfg_attr(wasm_bindgen, wasm_bindgen(js_name = toDisplaySafe))]
-    pub fn to_display_safe(self) -> String {
-        let mut bytes = vec![];
-        self.encode(&mut bytes).unwrap();
-        let mut result = String::from(SECRET_EMOJI);
-        result.push(VS16); // Ensure emoji presentation
+/// How a display-safe secret hides its bytes.
+///
+/// Every secret starts with the carrier's visible [`base`](DisplaySafeCarrier::base)
+/// character, which is how secrets are found in other text.
+pub trait DisplaySafeCarrier {
+    /// The visible character every secret starts with
+    fn base(&self) -> char;
+
+    /// Encode bytes as a secret
+    fn encode(&self, bytes: &[u8]) -> String;
+
+    /// Decode the secret starting at byte `start` of `text`, where the base character is.
+    /// Returns the end of the secret and its bytes.
+    fn decode_at(&self, text: &str, start: usize) -> Option<(usize, Vec<u8>)>;
+}
+
+/// Check that a base character is visible and unchanged by NFC and NFKC.
+fn check_base(base: char) -> Result<char, Error> {
+    let s = base.to_string();
+    // Controls, zero-width format characters, variation selectors and tags
+    let invisible = base.is_whitespace()
+        || base.is_control()
+        || matches!(base, '\u{AD}' | '\u{200B}'..='\u{200F}' | '\u{2060}'..='\u{206F}' | '\u{FEFF}')
+        || variation_selector_to_byte(base).is_some()
+        || matches!(base, '\u{E0000}'..='\u{E007F}');
+    if invisible || s.nfc().ne(s.chars()) || s.nfkc().ne(s.chars()) {
+        return Err(Error::InvalidFormat);
+    }
+    Ok(base)
+}
+
+/// Skip the base character and an emoji presentation selector some platforms add after it.
+fn skip_base(text: &str, start: usize, base: char) -> Option<usize> {
+    if !text.get(start..)?.starts_with(base) {
+        return None;
+    }
+    let end = start + base.len_utf8();
+    Some(match text[end..].starts_with(VS16) {
+        true => end + VS16.len_utf8(),
+        false => end,
+    })
+}
+
+/// One variation selector per byte: `U+FE00..U+FE0F` and `U+E0100..U+E01EF`.
+///
+/// This is the encoding of [`OkId::to_display_safe`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VariationSelectors {
+    emoji: char,
+    presentation: bool,
+}
+
+impl Default for VariationSelectors {
+    fn default() -> Self {
+        VariationSelectors {
+            emoji: SECRET_EMOJI,
+            presentation: true,
+        }
+    }
+}
+
+impl VariationSelectors {
+    /// Use `emoji` as the base. Fails with [`Error::InvalidFormat`] if it is invisible or
+    /// changed by NFC or NFKC normalisation.
+    pub fn new(emoji: char) -> Result<Self, Error> {
+        Ok(VariationSelectors {
+            emoji: check_base(emoji)?,
+            ..Default::default()
+        })
+    }
+
+    /// Whether to add the emoji presentation selector after the base. It is skipped when
+    /// decoding either way.
+    pub fn with_presentation(mut self, presentation: bool) -> Self {
+        self.presentation = presentation;
+        self
+    }
+}
+
+impl DisplaySafeCarrier for VariationSelectors {
+    fn base(&self) -> char {
+        self.emoji
+    }
+
+    fn encode(&self, bytes: &[u8]) -> String {
+        let mut result = String::from(self.emoji);
+        if self.presentation {
+            result.push(VS16); // Ensure emoji presentation
+        }
 
         // Convert each byte to a variation selector
         for byte in bytes {
-            result.push(byte_to_variation_selector(byte));
+            result.push(byte_to_variation_selector(*byte));
         }
         result
     }
 
+    fn decode_at(&self, text: &str, start: usize) -> Option<(usize, Vec<u8>)> {
+        let mut end = skip_base(text, start, self.emoji)?;
+        let mut bytes = Vec::new();
+        for ch in text[end..].chars() {
+            let Some(byte) = variation_selector_to_byte(ch) else {
+                break;
+            };
+            bytes.push(byte);
+            end += ch.len_utf8();
+        }
+        Some((end, bytes))
+    }
+}
+
+/// Two zero-width tag digits per byte, `U+E0030..U+E0039` and `U+E0061..U+E0066`, ended by
+/// a cancel tag like an emoji tag sequence.
+///
+/// Survives platforms that strip variation selectors.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Tags {
+    emoji: char,
+}
+
+impl Default for Tags {
+    fn default() -> Self {
+        Tags {
+            emoji: SECRET_EMOJI,
+        }
+    }
+}
+
+impl Tags {
+    /// Use `emoji` as the base. Fails like [`VariationSelectors::new`].
+    pub fn new(emoji: char) -> Result<Self, Error> {
+        Ok(Tags {
+            emoji: check_base(emoji)?,
+        })
+    }
+}
+
+impl DisplaySafeCarrier for Tags {
+    fn base(&self) -> char {
+        self.emoji
+    }
+
+    fn encode(&self, bytes: &[u8]) -> String {
+        let mut result = String::from(self.emoji);
+        for byte in bytes {
+            result.push(nibble_to_tag(byte >> 4));
+            result.push(nibble_to_tag(byte & 0xf));
+        }
+        result.push(CANCEL_TAG);
+        result
+    }
+
+    fn decode_at(&self, text: &str, start: usize) -> Option<(usize, Vec<u8>)> {
+        let mut end = skip_base(text, start, self.emoji)?;
+        let mut chars = text[end..].chars();
+        let mut bytes = Vec::new();
+        loop {
+            let high = chars.next()?;
+            if high == CANCEL_TAG {
+                return Some((end + CANCEL_TAG.len_utf8(), bytes));
+            }
+            let low = chars.next()?;
+            bytes.push(tag_to_nibble(high)? << 4 | tag_to_nibble(low)?);
+            end += high.len_utf8() + low.len_utf8();
+        }
+    }
+}
+
+#[cfg_attr(wasm_bindgen, wasm_bindgen)]
+impl OkId {
+    /// Embed an OkId inside a "secret" emoji using variation selectors
+    #[cfg_attr(wasm_bindgen, wasm_bindgen(js_name = toDisplaySafe))]
+    pub fn to_display_safe(self) -> String {
+        self.to_display_safe_with(&VariationSelectors::default())
+    }
+
     /// Helper function to decode a display_safe encoded OkId
+    ///
+    /// The whole string must be one secret; use [`scan_secrets`](crate::scan_secrets) to find
+    /// secrets in other text.
     #[cfg_attr(wasm_bindgen, wasm_bindgen(js_name = fromDisplaySafe))]
     pub fn from_display_safe(s: &str) -> Option<Self> {
-        let bytes = decode_variation_selectors(s)?;
+        OkId::from_display_safe_with(&VariationSelectors::default(), s)
+    }
+}
 
-        // Use from_bytes to avoid length mismatch issues
-        WireFormat::decode(&mut bytes.as_slice()).ok()
+impl OkId {
+    /// Embed an OkId with a [`DisplaySafeCarrier`].
+    pub fn to_display_safe_with(self, carrier: &(impl DisplaySafeCarrier + ?Sized)) -> String {
+        let mut bytes = vec![];
+        self.encode(&mut bytes).unwrap();
+        carrier.encode(&bytes)
+    }
+
+    /// Decode an OkId embedded with a [`DisplaySafeCarrier`]. The whole string must be one
+    /// secret.
+    pub fn from_display_safe_with(
+        carrier: &(impl DisplaySafeCarrier + ?Sized),
+        s: &str,
+    ) -> Option<Self> {
+        decode_secret(carrier, s, 0).and_then(|(end, id)| (end == s.len()).then_some(id))
     }
 }
 
@@ -64,28 +264,102 @@ const fn byte_to_variation_selector(byte: u8) -> char {
     }
 }
 
-/// Extract bytes from variation selectors
-fn decode_variation_selectors(s: &str) -> Option<Vec<u8>> {
-    let mut chars = s.chars();
+/// Decode the secret starting at byte `start` of `text`. Returns the end of the secret and
+/// its id.
+fn decode_secret(
+    carrier: &(impl DisplaySafeCarrier + ?Sized),
+    text: &str,
+    start: usize,
+) -> Option<(usize, OkId)> {
+    let (end, bytes) = carrier.decode_at(text, start)?;
+    let mut rest = bytes.as_slice();
+    let id: OkId = WireFormat::decode(&mut rest).ok()?;
+    rest.is_empty().then_some((end, id))
+}
 
-    // Check for the emoji prefix
-    if chars.next() != Some(SECRET_EMOJI) {
-        return None;
-    }
+/// Every display-safe secret embedded anywhere in `text`, with its byte range.
+///
+/// Lock emojis whose variation selectors do not decode to an OkId are skipped.
+///
+/// ```
+/// let id: okid::OkId = "fː0123456789abcdef".parse().unwrap();
+/// let text = format!("token {} leaked 🔒 here", id.to_display_safe());
+/// let found: Vec<_> = okid::scan_secrets(&text).collect();
+/// assert_eq!(found, [(6..6 + id.to_display_safe().len(), id)]);
+/// assert_eq!(okid::redact_secrets(&text, "[redacted]"), "token [redacted] leaked 🔒 here");
+/// ```
+pub fn scan_secrets(text: &str) -> impl Iterator<Item = (Range<usize>, OkId)> + '_ {
+    scan_secrets_with(VariationSelectors::default(), text)
+}
 
-    // Skip emoji presentation selector if present
-    if chars.clone().next() == Some(VS16) {
-        chars.next();
-    }
+/// Every secret embedded with `carrier` anywhere in `text`, with its byte range.
+pub fn scan_secrets_with<'a, C: DisplaySafeCarrier + 'a>(
+    carrier: C,
+    text: &'a str,
+) -> impl Iterator<Item = (Range<usize>, OkId)> + 'a {
+    let base = carrier.base();
+    text.match_indices(base).filter_map(move |(start, _)| {
+        decode_secret(&carrier, text, start).map(|(end, id)| (start..end, id))
+    })
+}
 
-    let mut result = Vec::new();
-    for ch in chars {
-        if let Some(byte) = variation_selector_to_byte(ch) {
-            result.push(byte);
-        }
+/// Streams every display-safe secret from a reader, with byte ranges from the start of the
+/// stream. Invalid UTF-8 between secrets is ignored.
+pub fn scan_secrets_reader<R: BufRead>(reader: R) -> ScanReader<R> {
+    scan_secrets_reader_with(VariationSelectors::default(), reader)
+}
+
+/// Streams every secret embedded with `carrier` from a reader, like [`scan_secrets_reader`].
+pub fn scan_secrets_reader_with<C, R>(carrier: C, reader: R) -> ScanReader<R>
+where
+    C: DisplaySafeCarrier + Copy + Send + Sync + 'static,
+    R: BufRead,
+{
+    ScanReader::new(
+        reader,
+        Box::new(move |line: &[u8]| {
+            let mut found = Vec::new();
+            let mut offset = 0;
+            for chunk in line.utf8_chunks() {
+                found.extend(
+                    scan_secrets_with(carrier, chunk.valid())
+                        .map(|(range, id)| (offset + range.start..offset + range.end, id)),
+                );
+                offset += chunk.valid().len() + chunk.invalid().len();
+            }
+            found
+        }),
+    )
+}
+
+/// Replace every display-safe secret in `text` with `replacement`.
+pub fn redact_secrets<'a>(text: &'a str, replacement: &str) -> Cow<'a, str> {
+    redact_secrets_with(VariationSelectors::default(), text, replacement)
+}
+
+/// Replace every secret embedded with `carrier` in `text` with `replacement`.
+pub fn redact_secrets_with<'a>(
+    carrier: impl DisplaySafeCarrier,
+    text: &'a str,
+    replacement: &str,
+) -> Cow<'a, str> {
+    let mut out = String::new();
+    let mut last = 0;
+    for (range, _) in scan_secrets_with(carrier, text) {
+        out.push_str(&text[last..range.start]);
+        out.push_str(replacement);
+        last = range.end;
+    }
+    if last == 0 {
+        return Cow::Borrowed(text);
     }
+    out.push_str(&text[last..]);
+    Cow::Owned(out)
+}
 
-    Some(result)
+/// Remove every display-safe secret from `text`.
+pub fn remove_secrets(text: &str) -> Cow<'_, str> {
+    redact_secrets(text, "")
 }
 
 /// Convert a variation selector character back to a byte
@@ -99,3 +373,24 @@ fn variation_selector_to_byte(variation_selector: char) -> Option<u8> {
         None
     }
 }
+
+/// Convert a nibble to a tag hex digit
+fn nibble_to_tag(nibble: u8) -> char {
+    let code = match nibble {
+        0..=9 => TAG_DIGIT_ZERO + nibble as u32,
+        _ => TAG_SMALL_A + (nibble - 10) as u32,
+    };
+    char::from_u32(code).unwrap()
+}
+
+/// Convert a tag hex digit back to a nibble
+fn tag_to_nibble(tag: char) -> Option<u8> {
+    let code = tag as u32;
+    if (TAG_DIGIT_ZERO..TAG_DIGIT_ZERO + 10).contains(&code) {
+        Some((code - TAG_DIGIT_ZERO) as u8)
+    } else if (TAG_SMALL_A..TAG_SMALL_A + 6).contains(&code) {
+        Some((code - TAG_SMALL_A) as u8 + 10)
+    } else {
+        None
+    }
+}
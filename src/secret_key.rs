@@ -0,0 +1,80 @@
+use {
+    crate::{hex_to_byte, BinaryType, Digest, Error, OkId},
+    ed25519_dalek::SigningKey,
+    std::{fmt::Display, str::FromStr},
+    zerocopy::{ByteEq, ByteHash, FromBytes, Immutable, IntoBytes, Unaligned},
+};
+
+pub(crate) const SECRET_KEY_LEN: usize = ed25519_dalek::SECRET_KEY_LENGTH;
+
+#[derive(Copy, Clone, ByteEq, Immutable, IntoBytes, ByteHash, FromBytes, Unaligned)]
+#[repr(C)]
+pub(super) struct SecretKey(pub(crate) [u8; SECRET_KEY_LEN]);
+
+impl std::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretKey(..)")
+    }
+}
+
+impl From<&SigningKey> for OkId {
+    fn from(value: &SigningKey) -> Self {
+        Self {
+            hash_type: BinaryType::SecretKey,
+            digest: Digest::SecretKey(SecretKey(value.to_bytes())),
+        }
+    }
+}
+
+impl crate::IntoOkId for &SigningKey {}
+
+impl TryFrom<OkId> for SigningKey {
+    type Error = Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        match value.digest {
+            Digest::SecretKey(secret_key) => Ok(SigningKey::from_bytes(&secret_key.0)),
+            _ => Err(Error::InvalidType),
+        }
+    }
+}
+
+impl Display for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let buf = &hex::encode(self.0);
+        f.write_str(buf)?;
+        Ok(())
+    }
+}
+
+impl FromStr for SecretKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let buf = hex::decode(s)?;
+        if buf.len() != SECRET_KEY_LEN {
+            return Err(Error::InvalidLength);
+        }
+        let mut key = [0; SECRET_KEY_LEN];
+        key.copy_from_slice(&buf);
+        Ok(SecretKey(key))
+    }
+}
+
+pub(crate) const fn parse_secret_key_bytes(bytes: &[u8], start: usize) -> Option<SecretKey> {
+    let mut result = [0u8; SECRET_KEY_LEN];
+    let mut i = 0;
+    while i < SECRET_KEY_LEN * 2 {
+        let high = match hex_to_byte(bytes[start + i]) {
+            Some(b) => b,
+            None => return None,
+        };
+        let low = match hex_to_byte(bytes[start + i + 1]) {
+            Some(b) => b,
+            None => return None,
+        };
+        result[i / 2] = (high << 4) | low;
+        i += 2;
+    }
+    Some(SecretKey(result))
+}
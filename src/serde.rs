@@ -49,6 +49,30 @@ impl Serialize for OkId {
                 state.serialize_field("digest", hex::encode(pub_key.0).as_str())?;
                 state.end()
             }
+            Digest::Signature(signature) => {
+                state.serialize_field("digest", hex::encode(signature.0).as_str())?;
+                state.end()
+            }
+            Digest::SecretKey(secret_key) => {
+                state.serialize_field("digest", hex::encode(secret_key.0).as_str())?;
+                state.end()
+            }
+            Digest::X25519(x25519) => {
+                state.serialize_field("digest", hex::encode(x25519.0).as_str())?;
+                state.end()
+            }
+            Digest::Secp256k1(secp256k1) => {
+                state.serialize_field("digest", hex::encode(secp256k1.0).as_str())?;
+                state.end()
+            }
+            Digest::P256(p256) => {
+                state.serialize_field("digest", hex::encode(p256.0).as_str())?;
+                state.end()
+            }
+            Digest::NixStore(nix_store) => {
+                state.serialize_field("digest", hex::encode(nix_store.0).as_str())?;
+                state.end()
+            }
         }
     }
 }
@@ -143,6 +167,30 @@ impl<'de> Visitor<'de> for OkIdVisitor {
                 crate::pub_key::PubKey::from_str(&digest_str)
                     .map_err(|_| serde::de::Error::custom("Invalid PubKey digest length"))?,
             ),
+            BinaryType::Signature => Digest::Signature(
+                crate::signature::Signature::from_str(&digest_str)
+                    .map_err(|_| serde::de::Error::custom("Invalid Signature digest length"))?,
+            ),
+            BinaryType::SecretKey => Digest::SecretKey(
+                crate::secret_key::SecretKey::from_str(&digest_str)
+                    .map_err(|_| serde::de::Error::custom("Invalid SecretKey digest length"))?,
+            ),
+            BinaryType::X25519 => Digest::X25519(
+                crate::x25519::X25519::from_str(&digest_str)
+                    .map_err(|_| serde::de::Error::custom("Invalid X25519 digest length"))?,
+            ),
+            BinaryType::Secp256k1 => Digest::Secp256k1(
+                crate::secp256k1::Secp256k1::from_str(&digest_str)
+                    .map_err(|_| serde::de::Error::custom("Invalid Secp256k1 digest length"))?,
+            ),
+            BinaryType::P256 => Digest::P256(
+                crate::p256::P256::from_str(&digest_str)
+                    .map_err(|_| serde::de::Error::custom("Invalid P256 digest length"))?,
+            ),
+            BinaryType::NixStore => Digest::NixStore(
+                crate::nix_store::NixStore::from_str(&digest_str)
+                    .map_err(|_| serde::de::Error::custom("Invalid NixStore digest length"))?,
+            ),
         };
 
         Ok(OkId { hash_type, digest })
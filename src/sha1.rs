@@ -50,6 +50,9 @@ impl FromStr for Sha1 {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let buf = hex::decode(s)?;
+        if buf.len() != 20 {
+            return Err(super::Error::InvalidLength);
+        }
         let mut hash: [u8; 20] = [0; 20];
         hash.copy_from_slice(&buf[..]);
         Ok(Sha1(hash))
@@ -43,6 +43,9 @@ impl FromStr for Sha256 {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let buf = hex::decode(s)?;
+        if buf.len() != 32 {
+            return Err(super::Error::InvalidLength);
+        }
         let mut hash: [u8; 32] = [0; 32];
         hash.copy_from_slice(&buf[..]);
         Ok(Sha256(hash))
@@ -48,6 +48,9 @@ impl FromStr for Sha512 {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let buf = hex::decode(s)?;
+        if buf.len() != 64 {
+            return Err(super::Error::InvalidLength);
+        }
         let mut hash: [u8; 64] = [0; 64];
         hash.copy_from_slice(&buf);
         Ok(Sha512(hash))
@@ -0,0 +1,163 @@
+//! Ed25519 signatures as `OkId`s.
+//!
+//! # Examples
+//! ```
+//! use okid::OkId;
+//!
+//! let (public, secret) = OkId::generate_keypair().unwrap();
+//! let signature = secret.sign(b"artifact bytes").unwrap();
+//! assert!(public.verify_signature(b"artifact bytes", &signature).is_ok());
+//! assert!(public.verify_signature(b"other bytes", &signature).is_err());
+//! ```
+
+#[cfg(wasm_bindgen)]
+use wasm_bindgen::prelude::*;
+use {
+    crate::{hex_to_byte, BinaryType, Digest, Error, IntoOkId, OkId},
+    ed25519_dalek::{Signer, SigningKey, VerifyingKey},
+    std::{fmt::Display, str::FromStr},
+    zerocopy::{ByteEq, ByteHash, FromBytes, Immutable, IntoBytes, Unaligned},
+};
+
+pub(crate) const SIGNATURE_LEN: usize = ed25519_dalek::SIGNATURE_LENGTH;
+
+#[derive(Copy, Clone, Debug, ByteEq, Immutable, IntoBytes, ByteHash, FromBytes, Unaligned)]
+#[repr(C)]
+#[cfg_attr(wasm_bindgen, wasm_bindgen)]
+pub(super) struct Signature(pub(crate) [u8; SIGNATURE_LEN]);
+
+impl From<ed25519_dalek::Signature> for OkId {
+    fn from(value: ed25519_dalek::Signature) -> Self {
+        Self {
+            hash_type: BinaryType::Signature,
+            digest: Digest::Signature(Signature(value.to_bytes())),
+        }
+    }
+}
+
+impl IntoOkId for ed25519_dalek::Signature {}
+
+impl TryFrom<OkId> for ed25519_dalek::Signature {
+    type Error = Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        match value.digest {
+            Digest::Signature(signature) => Ok(ed25519_dalek::Signature::from_bytes(&signature.0)),
+            _ => Err(Error::InvalidType),
+        }
+    }
+}
+
+impl TryFrom<OkId> for VerifyingKey {
+    type Error = Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        match value.digest {
+            Digest::PubKey(pub_key) => Ok(VerifyingKey::from_bytes(&pub_key.0)?),
+            _ => Err(Error::InvalidType),
+        }
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let buf = &hex::encode(self.0);
+        f.write_str(buf)?;
+        Ok(())
+    }
+}
+
+impl FromStr for Signature {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let buf = hex::decode(s)?;
+        if buf.len() != SIGNATURE_LEN {
+            return Err(Error::InvalidLength);
+        }
+        let mut signature = [0; SIGNATURE_LEN];
+        signature.copy_from_slice(&buf);
+        Ok(Signature(signature))
+    }
+}
+
+impl From<Signature> for Vec<u64> {
+    fn from(value: Signature) -> Self {
+        let data = value.0;
+        let mut out = [0; 8];
+        for i in 0..8 {
+            out[i] = u64::from_le_bytes(data[i * 8..(i + 1) * 8].try_into().unwrap());
+        }
+        out.to_vec()
+    }
+}
+
+pub(crate) const fn parse_signature_bytes(bytes: &[u8], start: usize) -> Option<Signature> {
+    let mut result = [0u8; SIGNATURE_LEN];
+    let mut i = 0;
+    while i < SIGNATURE_LEN * 2 {
+        let high = match hex_to_byte(bytes[start + i]) {
+            Some(b) => b,
+            None => return None,
+        };
+        let low = match hex_to_byte(bytes[start + i + 1]) {
+            Some(b) => b,
+            None => return None,
+        };
+        result[i / 2] = (high << 4) | low;
+        i += 2;
+    }
+    Some(Signature(result))
+}
+
+impl OkId {
+    /// Generate a new Ed25519 keypair.
+    ///
+    /// Returns the public key as a `pub_key` OkId and the secret key as a `secret_key` OkId.
+    /// The secret should only ever be shown through [`OkId::to_display_safe`].
+    pub fn generate_keypair() -> Result<(OkId, OkId), Error> {
+        let mut seed = [0u8; ed25519_dalek::SECRET_KEY_LENGTH];
+        getrandom::fill(&mut seed)?;
+        let signing_key = SigningKey::from_bytes(&seed);
+        Ok((
+            OkId::from(&signing_key.verifying_key()),
+            OkId::from(&signing_key),
+        ))
+    }
+
+    /// Sign `msg` with this secret key, returning a `signature` OkId.
+    pub fn sign(&self, msg: &[u8]) -> Result<OkId, Error> {
+        let signing_key = SigningKey::try_from(*self)?;
+        Ok(sign(&signing_key, msg))
+    }
+
+    /// Verify that `sig` is a valid signature of `msg` by this public key.
+    ///
+    /// Verification is strict: weak keys and malleable signatures are rejected.
+    pub fn verify_signature(&self, msg: &[u8], sig: &OkId) -> Result<(), Error> {
+        let verifying_key = VerifyingKey::try_from(*self)?;
+        let signature = ed25519_dalek::Signature::try_from(*sig)?;
+        Ok(verifying_key.verify_strict(msg, &signature)?)
+    }
+}
+
+/// Sign `msg` with `signing_key`, returning a `signature` OkId.
+pub fn sign(signing_key: &SigningKey, msg: impl AsRef<[u8]>) -> OkId {
+    signing_key.sign(msg.as_ref()).into()
+}
+
+#[cfg(wasm_bindgen)]
+#[wasm_bindgen]
+impl OkId {
+    /// Sign a message with this secret key
+    #[wasm_bindgen(js_name = sign)]
+    pub fn js_sign(&self, msg: &[u8]) -> Result<OkId, JsError> {
+        self.sign(msg).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Verify a signature of a message against this public key
+    #[wasm_bindgen(js_name = verifySignature)]
+    pub fn js_verify_signature(&self, msg: &[u8], sig: &OkId) -> bool {
+        self.verify_signature(msg, sig).is_ok()
+    }
+}
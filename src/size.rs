@@ -32,6 +32,12 @@ impl OkId {
             super::BinaryType::Uuid => 16,
             super::BinaryType::Fingerprint => 8,
             super::BinaryType::PubKey => 32,
+            super::BinaryType::Signature => 64,
+            super::BinaryType::SecretKey => 32,
+            super::BinaryType::X25519 => 32,
+            super::BinaryType::Secp256k1 => 33,
+            super::BinaryType::P256 => 33,
+            super::BinaryType::NixStore => 20,
             super::BinaryType::Unknown => 0,
         }
     }
@@ -0,0 +1,316 @@
+//! Subresource Integrity strings, as used by HTML `integrity=` attributes and npm lockfiles.
+//!
+//! Only `sha256` entries have an OkId kind. `sha384` and `sha512` entries still parse and
+//! verify, but converting them fails with [`Error::UnsupportedAlgorithm`].
+//!
+//! # Examples
+//! ```
+//! use okid::{sri::Integrity, OkId};
+//!
+//! let id: OkId = "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+//!     .parse()
+//!     .unwrap();
+//! assert_eq!(
+//!     id.to_sri().unwrap(),
+//!     "sha256-uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek="
+//! );
+//!
+//! let integrity: Integrity = "sha256-uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek= md5-AAAA"
+//!     .parse()
+//!     .unwrap();
+//! assert!(integrity.verify(b"hello world"));
+//! assert_eq!(integrity.ids(), vec![id]);
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sha2::Digest as _;
+#[cfg(wasm_bindgen)]
+use wasm_bindgen::prelude::*;
+
+use crate::{BinaryType, Error, OkId};
+
+/// A hash algorithm allowed in integrity metadata, ordered from weakest to strongest.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Algorithm {
+    /// `sha256`
+    Sha256,
+    /// `sha384`
+    Sha384,
+    /// `sha512`
+    Sha512,
+}
+
+impl Algorithm {
+    fn digest_len(self) -> usize {
+        match self {
+            Algorithm::Sha256 => 32,
+            Algorithm::Sha384 => 48,
+            Algorithm::Sha512 => 64,
+        }
+    }
+
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Algorithm::Sha256 => sha2::Sha256::digest(data).to_vec(),
+            Algorithm::Sha384 => sha2::Sha384::digest(data).to_vec(),
+            Algorithm::Sha512 => sha2::Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha384 => "sha384",
+            Algorithm::Sha512 => "sha512",
+        })
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha256" => Ok(Algorithm::Sha256),
+            "sha384" => Ok(Algorithm::Sha384),
+            "sha512" => Ok(Algorithm::Sha512),
+            _ => Err(Error::UnsupportedAlgorithm(s.to_string())),
+        }
+    }
+}
+
+/// One `<algorithm>-<base64>[?<options>]` entry of integrity metadata.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Hash {
+    /// Hash algorithm
+    pub algorithm: Algorithm,
+    /// Raw digest
+    pub digest: Vec<u8>,
+    /// Options after `?`, kept but not interpreted
+    pub options: Option<String>,
+}
+
+impl Hash {
+    /// Hash `data` with `algorithm`.
+    pub fn new(algorithm: Algorithm, data: &[u8]) -> Self {
+        Hash {
+            algorithm,
+            digest: algorithm.digest(data),
+            options: None,
+        }
+    }
+
+    /// Whether `data` hashes to this entry.
+    pub fn verify(&self, data: &[u8]) -> bool {
+        self.algorithm.digest(data) == self.digest
+    }
+}
+
+impl fmt::Display for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.algorithm, STANDARD.encode(&self.digest))?;
+        if let Some(options) = &self.options {
+            write!(f, "?{}", options)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Hash {
+    type Err = Error;
+
+    /// Parse a single entry. Unknown algorithms fail with [`Error::UnsupportedAlgorithm`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (algorithm, rest) = s.split_once('-').ok_or(Error::InvalidFormat)?;
+        let algorithm = algorithm.parse::<Algorithm>()?;
+        let (encoded, options) = match rest.split_once('?') {
+            Some((encoded, options)) => (encoded, Some(options.to_string())),
+            None => (rest, None),
+        };
+        let digest = STANDARD.decode(encoded).map_err(|_| Error::InvalidFormat)?;
+        if digest.len() != algorithm.digest_len() {
+            return Err(Error::InvalidLength);
+        }
+        Ok(Hash {
+            algorithm,
+            digest,
+            options,
+        })
+    }
+}
+
+impl TryFrom<&Hash> for OkId {
+    type Error = Error;
+
+    fn try_from(hash: &Hash) -> Result<Self, Self::Error> {
+        match hash.algorithm {
+            Algorithm::Sha256 => OkId::from_digest_bytes(BinaryType::Sha256, &hash.digest),
+            algorithm => Err(Error::UnsupportedAlgorithm(algorithm.to_string())),
+        }
+    }
+}
+
+impl TryFrom<OkId> for Hash {
+    type Error = Error;
+
+    fn try_from(id: OkId) -> Result<Self, Self::Error> {
+        match id.hash_type {
+            BinaryType::Sha256 => Ok(Hash {
+                algorithm: Algorithm::Sha256,
+                digest: id.digest_bytes(),
+                options: None,
+            }),
+            _ => Err(Error::InvalidType),
+        }
+    }
+}
+
+/// Integrity metadata: a whitespace separated list of [`Hash`]es.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Integrity {
+    hashes: Vec<Hash>,
+}
+
+impl Integrity {
+    /// Hash `data` with each of `algorithms`, e.g. to emit `sha384-… sha512-…`.
+    pub fn generate(data: &[u8], algorithms: &[Algorithm]) -> Self {
+        Integrity {
+            hashes: algorithms
+                .iter()
+                .map(|algorithm| Hash::new(*algorithm, data))
+                .collect(),
+        }
+    }
+
+    /// All entries, in the order they were written.
+    pub fn hashes(&self) -> &[Hash] {
+        &self.hashes
+    }
+
+    /// The entries of the strongest algorithm present, the only ones used for verification.
+    pub fn strongest(&self) -> impl Iterator<Item = &Hash> {
+        let strongest = self.hashes.iter().map(|hash| hash.algorithm).max();
+        self.hashes
+            .iter()
+            .filter(move |hash| Some(hash.algorithm) == strongest)
+    }
+
+    /// The OkIds of the entries that have an OkId kind.
+    pub fn ids(&self) -> Vec<OkId> {
+        self.hashes
+            .iter()
+            .filter_map(|hash| OkId::try_from(hash).ok())
+            .collect()
+    }
+
+    /// Whether `data` matches any entry of the strongest algorithm, as browsers check it.
+    pub fn verify(&self, data: &[u8]) -> bool {
+        let mut strongest = self.strongest().peekable();
+        let Some(first) = strongest.peek() else {
+            return false;
+        };
+        let digest = first.algorithm.digest(data);
+        strongest.any(|hash| hash.digest == digest)
+    }
+}
+
+impl fmt::Display for Integrity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, hash) in self.hashes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", hash)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Integrity {
+    type Err = Error;
+
+    /// Parse integrity metadata.
+    ///
+    /// Entries with unknown algorithms are skipped, as the SRI spec requires, but malformed
+    /// entries of known algorithms are errors, as is metadata with no usable entries.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut hashes = Vec::new();
+        for token in s.split_ascii_whitespace() {
+            match token.parse::<Hash>() {
+                Ok(hash) => hashes.push(hash),
+                Err(Error::UnsupportedAlgorithm(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        if hashes.is_empty() {
+            return Err(Error::InvalidFormat);
+        }
+        Ok(Integrity { hashes })
+    }
+}
+
+impl TryFrom<OkId> for Integrity {
+    type Error = Error;
+
+    fn try_from(id: OkId) -> Result<Self, Self::Error> {
+        Ok(Integrity {
+            hashes: vec![Hash::try_from(id)?],
+        })
+    }
+}
+
+impl OkId {
+    /// Format as an SRI string such as `sha256-…`.
+    pub fn to_sri(&self) -> Result<String, Error> {
+        Ok(Hash::try_from(*self)?.to_string())
+    }
+
+    /// Parse integrity metadata into the OkId of its strongest entry.
+    pub fn from_sri(s: &str) -> Result<OkId, Error> {
+        let integrity = s.parse::<Integrity>()?;
+        let strongest = integrity.strongest().next().ok_or(Error::InvalidFormat)?;
+        OkId::try_from(strongest)
+    }
+}
+
+#[cfg(wasm_bindgen)]
+#[wasm_bindgen]
+impl OkId {
+    /// Format as an SRI `integrity` attribute value
+    #[wasm_bindgen(js_name = toSri)]
+    pub fn js_to_sri(&self) -> Result<String, JsError> {
+        self.to_sri().map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Parse the strongest entry of an SRI `integrity` attribute value
+    #[wasm_bindgen(js_name = fromSri)]
+    pub fn js_from_sri(s: &str) -> Result<OkId, JsError> {
+        OkId::from_sri(s).map_err(|e| JsError::new(&e.to_string()))
+    }
+}
+
+/// Generate an SRI `integrity` attribute value for `data` with space separated `algorithms`
+#[cfg(wasm_bindgen)]
+#[wasm_bindgen(js_name = sriGenerate)]
+pub fn js_sri_generate(data: &[u8], algorithms: &str) -> Result<String, JsError> {
+    let algorithms = algorithms
+        .split_ascii_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<Algorithm>, _>>()
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(Integrity::generate(data, &algorithms).to_string())
+}
+
+/// Check `data` against an SRI `integrity` attribute value
+#[cfg(wasm_bindgen)]
+#[wasm_bindgen(js_name = sriVerify)]
+pub fn js_sri_verify(integrity: &str, data: &[u8]) -> Result<bool, JsError> {
+    let integrity = integrity
+        .parse::<Integrity>()
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(integrity.verify(data))
+}
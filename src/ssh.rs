@@ -0,0 +1,208 @@
+//! OpenSSH public key and fingerprint interop.
+//!
+//! # Examples
+//! ```
+//! use okid::OkId;
+//!
+//! let line = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIILi2uwTjjQM17fB66zrLJc2ZsbtmP7y5maSGYEHh9XJ host@example";
+//! let key = OkId::from_openssh(line).unwrap();
+//! assert_eq!(
+//!     key.to_openssh_fingerprint().unwrap(),
+//!     "SHA256:BJKJWaCoBdUmUJzy0qvu3gPBavUfQjDSkFrDFV023/g"
+//! );
+//! ```
+
+use base64::{
+    engine::general_purpose::{STANDARD, STANDARD_NO_PAD},
+    Engine,
+};
+use md5::Digest as _;
+
+use crate::{BinaryType, Digest, Error, OkId};
+
+const SSH_ED25519: &str = "ssh-ed25519";
+const SHA256_PREFIX: &str = "SHA256:";
+const MD5_PREFIX: &str = "MD5:";
+
+/// A single entry of an OpenSSH `authorized_keys` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthorizedKey {
+    /// Options preceding the key, e.g. `no-pty,from="10.0.0.0/8"`
+    pub options: Option<String>,
+    /// The public key
+    pub key: OkId,
+    /// Trailing comment
+    pub comment: Option<String>,
+}
+
+impl OkId {
+    /// Parse an OpenSSH public key line such as `ssh-ed25519 AAAA… comment`.
+    pub fn from_openssh(line: &str) -> Result<OkId, Error> {
+        let mut fields = line.split_whitespace();
+        let key_type = fields.next().ok_or(Error::InvalidFormat)?;
+        let blob = fields.next().ok_or(Error::InvalidFormat)?;
+        decode_key(key_type, blob)
+    }
+
+    /// Format this public key as an OpenSSH public key line.
+    pub fn to_openssh(&self, comment: Option<&str>) -> Result<String, Error> {
+        let mut line = format!("{} {}", SSH_ED25519, STANDARD.encode(key_blob(self)?));
+        if let Some(comment) = comment {
+            line.push(' ');
+            line.push_str(comment);
+        }
+        Ok(line)
+    }
+
+    /// The SHA-256 digest of this public key's OpenSSH wire encoding.
+    pub fn ssh_fingerprint(&self) -> Result<OkId, Error> {
+        Ok(sha2::Sha256::new_with_prefix(key_blob(self)?).into())
+    }
+
+    /// Format as an OpenSSH `SHA256:…` fingerprint.
+    ///
+    /// Public keys are hashed first; SHA-256 ids are assumed to already be a fingerprint.
+    pub fn to_openssh_fingerprint(&self) -> Result<String, Error> {
+        let fingerprint = match self.digest {
+            Digest::Sha256(_) => *self,
+            _ => self.ssh_fingerprint()?,
+        };
+        match fingerprint.digest {
+            Digest::Sha256(sha256) => Ok(format!(
+                "{}{}",
+                SHA256_PREFIX,
+                STANDARD_NO_PAD.encode(sha256.0)
+            )),
+            _ => Err(Error::InvalidType),
+        }
+    }
+
+    /// Format as a legacy OpenSSH `MD5:aa:bb:…` fingerprint.
+    ///
+    /// OkId has no MD5 kind, so this is one-way.
+    pub fn to_openssh_md5_fingerprint(&self) -> Result<String, Error> {
+        let digest = md5::Md5::digest(key_blob(self)?);
+        let hex = digest
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(":");
+        Ok(format!("{}{}", MD5_PREFIX, hex))
+    }
+
+    /// Parse an OpenSSH `SHA256:…` fingerprint into a SHA-256 OkId.
+    pub fn from_openssh_fingerprint(s: &str) -> Result<OkId, Error> {
+        let encoded = s.strip_prefix(SHA256_PREFIX).ok_or(Error::InvalidFormat)?;
+        let bytes = STANDARD_NO_PAD
+            .decode(encoded.trim_end_matches('='))
+            .map_err(|_| Error::InvalidFormat)?;
+        if bytes.len() != 32 {
+            return Err(Error::InvalidLength);
+        }
+        let mut buf = [0u8; 32];
+        buf.copy_from_slice(&bytes);
+        Ok(OkId {
+            hash_type: BinaryType::Sha256,
+            digest: Digest::Sha256(crate::sha2::Sha256(buf)),
+        })
+    }
+}
+
+/// Parse the entries of an OpenSSH `authorized_keys` file.
+///
+/// Blank lines and comments are skipped. Entries with key types other than `ssh-ed25519`
+/// yield [`Error::InvalidType`] so callers can decide whether to ignore them.
+pub fn parse_authorized_keys(s: &str) -> impl Iterator<Item = Result<AuthorizedKey, Error>> + '_ {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_authorized_key)
+}
+
+fn parse_authorized_key(line: &str) -> Result<AuthorizedKey, Error> {
+    let (options, rest) = if is_key_type(line.split_whitespace().next().unwrap_or_default()) {
+        (None, line)
+    } else {
+        let (options, rest) = split_options(line)?;
+        (Some(options.to_string()), rest.trim_start())
+    };
+    let mut fields = rest.splitn(3, char::is_whitespace);
+    let key_type = fields.next().ok_or(Error::InvalidFormat)?;
+    let blob = fields.next().ok_or(Error::InvalidFormat)?;
+    let comment = fields
+        .next()
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(String::from);
+    Ok(AuthorizedKey {
+        options,
+        key: decode_key(key_type, blob)?,
+        comment,
+    })
+}
+
+fn is_key_type(s: &str) -> bool {
+    s.starts_with("ssh-") || s.starts_with("ecdsa-") || s.starts_with("sk-")
+}
+
+/// Split the options field off the front of a line, honouring double quotes.
+fn split_options(line: &str) -> Result<(&str, &str), Error> {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => return Ok(line.split_at(i)),
+            _ => {}
+        }
+    }
+    Err(Error::InvalidFormat)
+}
+
+fn decode_key(key_type: &str, blob: &str) -> Result<OkId, Error> {
+    if key_type != SSH_ED25519 {
+        return Err(Error::InvalidType);
+    }
+    let blob = STANDARD.decode(blob).map_err(|_| Error::InvalidFormat)?;
+    let mut reader = blob.as_slice();
+    if read_string(&mut reader)? != SSH_ED25519.as_bytes() {
+        return Err(Error::InvalidType);
+    }
+    let key = read_string(&mut reader)?;
+    if key.len() != 32 || !reader.is_empty() {
+        return Err(Error::InvalidLength);
+    }
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(key);
+    Ok(OkId {
+        hash_type: BinaryType::PubKey,
+        digest: Digest::PubKey(crate::pub_key::PubKey(buf)),
+    })
+}
+
+fn read_string<'a>(reader: &mut &'a [u8]) -> Result<&'a [u8], Error> {
+    if reader.len() < 4 {
+        return Err(Error::InvalidLength);
+    }
+    let (len, rest) = reader.split_at(4);
+    let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize;
+    if rest.len() < len {
+        return Err(Error::InvalidLength);
+    }
+    let (string, rest) = rest.split_at(len);
+    *reader = rest;
+    Ok(string)
+}
+
+/// The OpenSSH wire encoding of a public key.
+fn key_blob(id: &OkId) -> Result<Vec<u8>, Error> {
+    let key = match id.digest {
+        Digest::PubKey(pub_key) => pub_key.0,
+        _ => return Err(Error::InvalidType),
+    };
+    let mut blob = Vec::with_capacity(4 + SSH_ED25519.len() + 4 + key.len());
+    blob.extend_from_slice(&(SSH_ED25519.len() as u32).to_be_bytes());
+    blob.extend_from_slice(SSH_ED25519.as_bytes());
+    blob.extend_from_slice(&(key.len() as u32).to_be_bytes());
+    blob.extend_from_slice(&key);
+    Ok(blob)
+}
@@ -0,0 +1,256 @@
+//! Finding OkIds in URLs and building URLs that contain them.
+//!
+//! The separator `ː` is not URL-safe and must appear as `%CB%90` in links. [`OkId::to_url`] and
+//! [`OkId::to_path_segment`] encode it, and every lookup here decodes it again.
+//!
+//! # Examples
+//! ```
+//! use okid::{url::Route, OkId};
+//! use url::Url;
+//!
+//! let id: OkId = "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9".parse().unwrap();
+//! let base = Url::parse("https://example.com/").unwrap();
+//! let url = id.to_url(&base, "/files/{okid}/hello.txt").unwrap();
+//! assert_eq!(
+//!     url.as_str(),
+//!     "https://example.com/files/2%CB%90b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9/hello.txt"
+//! );
+//!
+//! let matched = Route::new("/files/{okid}/{name}").unwrap().matches(url.path()).unwrap();
+//! assert_eq!(matched.id, id);
+//! assert_eq!(matched.param("name"), Some("hello.txt"));
+//! ```
+
+use std::{borrow::Cow, collections::BTreeMap};
+
+use ::url::Url;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+#[cfg(wasm_bindgen)]
+use wasm_bindgen::prelude::*;
+
+use crate::{parse::parse_okid, Error, OkId};
+
+/// The placeholder for the OkId in [`OkId::to_url`] templates and [`Route`] patterns.
+pub const PLACEHOLDER: &str = "{okid}";
+
+// Everything but unreserved characters, so a segment is also safe in queries and fragments
+const SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'&')
+    .add(b'+')
+    .add(b'/')
+    .add(b'<')
+    .add(b'=')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+fn decode(s: &str) -> Cow<'_, str> {
+    percent_decode_str(s).decode_utf8_lossy()
+}
+
+/// The OkIds in the values of `&`-separated `name=value` pairs, or in bare values.
+fn pairs<'a>(s: &'a str, name: Option<&'a str>) -> impl Iterator<Item = Result<OkId, Error>> + 'a {
+    s.split('&').filter_map(move |pair| {
+        let (key, value) = match pair.split_once('=') {
+            Some((key, value)) => (Some(key), value),
+            None => (None, pair),
+        };
+        match name {
+            Some(name) if key.map(decode).as_deref() != Some(name) => None,
+            _ => Some(parse_okid(&decode(&value.replace('+', " ")))),
+        }
+    })
+}
+
+fn lookup(s: Option<&str>, name: &str) -> Result<OkId, Error> {
+    pairs(s.unwrap_or_default(), Some(name))
+        .next()
+        .unwrap_or(Err(Error::NotFound))
+}
+
+impl OkId {
+    /// The display form with the separator percent-encoded, for use in any part of a URL.
+    ///
+    /// ```
+    /// let id = okid::okid!("uː4d3881627191c1d4236405ac98409b01");
+    /// assert_eq!(id.to_path_segment(), "u%CB%904d3881627191c1d4236405ac98409b01");
+    /// ```
+    pub fn to_path_segment(&self) -> String {
+        utf8_percent_encode(&self.to_string(), SEGMENT).to_string()
+    }
+
+    /// Build a URL by replacing [`PLACEHOLDER`] in `template` with the encoded OkId and
+    /// resolving the result against `base`.
+    ///
+    /// The template may place the OkId in the path, query or fragment. It fails with
+    /// [`Error::InvalidFormat`] if the template has no placeholder or does not resolve.
+    pub fn to_url(&self, base: &Url, template: &str) -> Result<Url, Error> {
+        if !template.contains(PLACEHOLDER) {
+            return Err(Error::InvalidFormat);
+        }
+        base.join(&template.replace(PLACEHOLDER, &self.to_path_segment()))
+            .map_err(|_| Error::InvalidFormat)
+    }
+
+    /// Parse the OkId in the query parameter `name`.
+    ///
+    /// Fails with [`Error::NotFound`] if there is no such parameter, or with the parse error of
+    /// the first one.
+    ///
+    /// ```
+    /// use okid::OkId;
+    /// use url::Url;
+    ///
+    /// let url = Url::parse("https://example.com/?v=2&id=u%CB%904d3881627191c1d4236405ac98409b01").unwrap();
+    /// let id = OkId::from_query(&url, "id").unwrap();
+    /// assert_eq!(id.to_string(), "uː4d3881627191c1d4236405ac98409b01");
+    /// ```
+    pub fn from_query(url: &Url, name: &str) -> Result<OkId, Error> {
+        lookup(url.query(), name)
+    }
+
+    /// Parse the OkId in the fragment parameter `name`, for fragments of the form
+    /// `#name=value&other=value`.
+    ///
+    /// Fails like [`OkId::from_query`].
+    pub fn from_fragment(url: &Url, name: &str) -> Result<OkId, Error> {
+        lookup(url.fragment(), name)
+    }
+}
+
+/// All OkIds in a URL, in the order they appear: path segments, then query values, then
+/// fragment values or a bare fragment.
+///
+/// ```
+/// use url::Url;
+///
+/// let url = Url::parse(
+///     "https://example.com/u%CB%904d3881627191c1d4236405ac98409b01?x=1#fː0000000000000000",
+/// )
+/// .unwrap();
+/// let ids = okid::url::find_all(&url);
+/// assert_eq!(ids.len(), 2);
+/// ```
+pub fn find_all(url: &Url) -> Vec<OkId> {
+    let segments = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .map(|segment| parse_okid(&decode(segment)));
+    segments
+        .chain(pairs(url.query().unwrap_or_default(), None))
+        .chain(pairs(url.fragment().unwrap_or_default(), None))
+        .filter_map(Result::ok)
+        .collect()
+}
+
+/// A path pattern such as `/files/{okid}/{name}`.
+///
+/// Segments are literals, [`PLACEHOLDER`] (exactly once) or named parameters in braces that
+/// match any single segment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    OkId,
+    Param(String),
+}
+
+/// A path matched by a [`Route`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RouteMatch {
+    /// The OkId in the [`PLACEHOLDER`] segment
+    pub id: OkId,
+    /// The decoded named parameters
+    pub params: BTreeMap<String, String>,
+}
+
+impl RouteMatch {
+    /// A named parameter.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+}
+
+impl Route {
+    /// Parse a pattern. Fails with [`Error::InvalidFormat`] unless [`PLACEHOLDER`] is exactly
+    /// one whole segment and parameter names are unique.
+    pub fn new(pattern: &str) -> Result<Self, Error> {
+        let mut segments = Vec::new();
+        for segment in pattern.trim_start_matches('/').split('/') {
+            let segment = if segment == PLACEHOLDER {
+                if segments.contains(&Segment::OkId) {
+                    return Err(Error::InvalidFormat);
+                }
+                Segment::OkId
+            } else if let Some(name) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                let param = Segment::Param(name.to_string());
+                if name.is_empty() || name.contains(['{', '}']) || segments.contains(&param) {
+                    return Err(Error::InvalidFormat);
+                }
+                param
+            } else if segment.contains(['{', '}']) {
+                return Err(Error::InvalidFormat);
+            } else {
+                Segment::Literal(segment.to_string())
+            };
+            segments.push(segment);
+        }
+        if !segments.contains(&Segment::OkId) {
+            return Err(Error::InvalidFormat);
+        }
+        Ok(Route { segments })
+    }
+
+    /// Match a percent-encoded path such as [`Url::path`], segment by segment.
+    pub fn matches(&self, path: &str) -> Option<RouteMatch> {
+        let path = path.trim_start_matches('/');
+        if path.split('/').count() != self.segments.len() {
+            return None;
+        }
+        let mut id = None;
+        let mut params = BTreeMap::new();
+        for (segment, value) in self.segments.iter().zip(path.split('/')) {
+            let value = decode(value);
+            match segment {
+                Segment::Literal(literal) if *literal == value => {}
+                Segment::Literal(_) => return None,
+                Segment::OkId => id = Some(parse_okid(&value).ok()?),
+                Segment::Param(name) => {
+                    params.insert(name.clone(), value.into_owned());
+                }
+            }
+        }
+        Some(RouteMatch { id: id?, params })
+    }
+}
+
+#[cfg(wasm_bindgen)]
+#[wasm_bindgen]
+impl OkId {
+    /// Build a URL from a base and a template containing `{okid}`
+    #[wasm_bindgen(js_name = toUrl)]
+    pub fn js_to_url(&self, base: &str, template: &str) -> Result<String, JsError> {
+        let base = Url::parse(base).map_err(|e| JsError::new(&e.to_string()))?;
+        self.to_url(&base, template)
+            .map(String::from)
+            .map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Parse the OkId in a query parameter of a URL
+    #[wasm_bindgen(js_name = fromQuery)]
+    pub fn js_from_query(url: &str, name: &str) -> Result<OkId, JsError> {
+        let url = Url::parse(url).map_err(|e| JsError::new(&e.to_string()))?;
+        OkId::from_query(&url, name).map_err(|e| JsError::new(&e.to_string()))
+    }
+}
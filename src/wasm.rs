@@ -3,7 +3,7 @@ use std::collections::hash_map::DefaultHasher;
 use std::str::FromStr;
 use wasm_bindgen::prelude::*;
 
-use crate::to_ascii;
+use crate::{okid::from_path_segments, to_ascii};
 
 use super::OkId;
 
@@ -16,14 +16,9 @@ impl OkId {
     }
 
     #[wasm_bindgen]
-    /// Parse an OkId from a URL path
+    /// Parse the first OkId among the segments of a URL path
     pub fn from_path(u: web_sys::Url) -> Result<OkId, JsError> {
-        let path = u.pathname();
-        let path = path.trim_start_matches('/');
-        let path = percent_encoding::percent_decode_str(path)
-            .decode_utf8()
-            .map_err(|e| JsError::new(&e.to_string()))?;
-        OkId::from_string(&path)
+        from_path_segments(u.pathname().split('/')).map_err(|e| JsError::new(&e.to_string()))
     }
 
     /// Convert the OkId to a string
@@ -44,6 +39,12 @@ impl OkId {
         to_ascii(*self)
     }
 
+    /// Parse the ASCII/path-safe format, with any sharding
+    #[wasm_bindgen(js_name = fromAscii)]
+    pub fn js_from_ascii(s: &str) -> Result<OkId, JsError> {
+        OkId::from_ascii(s).map_err(|e| JsError::new(&e.to_string()))
+    }
+
     /// Create an OkId from a SHA256 hash
     #[cfg(feature = "sha2")]
     #[wasm_bindgen(js_name = fromSha256)]
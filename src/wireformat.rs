@@ -25,6 +25,12 @@ impl jetstream_wireformat::WireFormat for OkId {
             Digest::Uuid(_uuid) => 128 / 8,
             Digest::Fingerprint(_fingerprint) => 64 / 8,
             Digest::PubKey(_pub_key) => 32,
+            Digest::Signature(signature) => signature.0.len() as u32,
+            Digest::SecretKey(secret_key) => secret_key.0.len() as u32,
+            Digest::X25519(x25519) => x25519.0.len() as u32,
+            Digest::Secp256k1(secp256k1) => secp256k1.0.len() as u32,
+            Digest::P256(p256) => p256.0.len() as u32,
+            Digest::NixStore(nix_store) => nix_store.0.len() as u32,
 
         }
     }
@@ -51,6 +57,12 @@ impl jetstream_wireformat::WireFormat for OkId {
                 writer.write_all(fingerprint.0.as_bytes())?;
             }
             Digest::PubKey(pub_key) => writer.write_all(&pub_key.0)?,
+            Digest::Signature(signature) => writer.write_all(&signature.0)?,
+            Digest::SecretKey(secret_key) => writer.write_all(&secret_key.0)?,
+            Digest::X25519(x25519) => writer.write_all(&x25519.0)?,
+            Digest::Secp256k1(secp256k1) => writer.write_all(&secp256k1.0)?,
+            Digest::P256(p256) => writer.write_all(&p256.0)?,
+            Digest::NixStore(nix_store) => writer.write_all(&nix_store.0)?,
         }
 
         Ok(())
@@ -131,6 +143,95 @@ impl jetstream_wireformat::WireFormat for OkId {
                     digest: Digest::PubKey(crate::pub_key::PubKey(buf)),
                 })
             }
+            BinaryType::Signature => {
+                let mut buf = [0; 64];
+                reader.read_exact(&mut buf)?;
+                Ok(OkId {
+                    hash_type: BinaryType::Signature,
+                    digest: Digest::Signature(crate::signature::Signature(buf)),
+                })
+            }
+            BinaryType::SecretKey => {
+                let mut buf = [0; 32];
+                reader.read_exact(&mut buf)?;
+                Ok(OkId {
+                    hash_type: BinaryType::SecretKey,
+                    digest: Digest::SecretKey(crate::secret_key::SecretKey(buf)),
+                })
+            }
+            BinaryType::X25519 => {
+                let mut buf = [0; 32];
+                reader.read_exact(&mut buf)?;
+                Ok(OkId {
+                    hash_type: BinaryType::X25519,
+                    digest: Digest::X25519(crate::x25519::X25519(buf)),
+                })
+            }
+            BinaryType::Secp256k1 => {
+                let mut buf = [0; 33];
+                reader.read_exact(&mut buf)?;
+                Ok(OkId {
+                    hash_type: BinaryType::Secp256k1,
+                    digest: Digest::Secp256k1(crate::secp256k1::Secp256k1(buf)),
+                })
+            }
+            BinaryType::P256 => {
+                let mut buf = [0; 33];
+                reader.read_exact(&mut buf)?;
+                Ok(OkId {
+                    hash_type: BinaryType::P256,
+                    digest: Digest::P256(crate::p256::P256(buf)),
+                })
+            }
+            BinaryType::NixStore => {
+                let mut buf = [0; 20];
+                reader.read_exact(&mut buf)?;
+                Ok(OkId {
+                    hash_type: BinaryType::NixStore,
+                    digest: Digest::NixStore(crate::nix_store::NixStore(buf)),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "did",
+    feature = "jwk",
+    feature = "cid",
+    feature = "oci",
+    feature = "git",
+    feature = "nix",
+    feature = "sri",
+    feature = "ni",
+    feature = "digest-set",
+    feature = "http"
+))]
+impl OkId {
+    /// The raw digest bytes, without the leading type code.
+    pub(crate) fn digest_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.encoded_size());
+        jetstream_wireformat::WireFormat::encode(self, &mut buf).unwrap();
+        buf.split_off(1)
+    }
+
+    /// Build an OkId of `hash_type` from its raw digest bytes.
+    pub(crate) fn from_digest_bytes(
+        hash_type: BinaryType,
+        bytes: &[u8],
+    ) -> Result<OkId, crate::Error> {
+        if hash_type == BinaryType::Unknown {
+            return Err(crate::Error::InvalidDigestType);
+        }
+        let mut buf = Vec::with_capacity(bytes.len() + 1);
+        buf.push(hash_type.char_code() as u8);
+        buf.extend_from_slice(bytes);
+        let mut reader = buf.as_slice();
+        let id = <OkId as jetstream_wireformat::WireFormat>::decode(&mut reader)
+            .map_err(|_| crate::Error::InvalidLength)?;
+        if !reader.is_empty() {
+            return Err(crate::Error::InvalidLength);
         }
+        Ok(id)
     }
 }
@@ -0,0 +1,78 @@
+#[cfg(feature = "x25519")]
+use crate::{BinaryType, Digest, OkId};
+use {
+    crate::{hex_to_byte, Error},
+    std::{fmt::Display, str::FromStr},
+    zerocopy::{ByteEq, ByteHash, FromBytes, Immutable, IntoBytes, Unaligned},
+};
+
+pub(crate) const X25519_LEN: usize = 32;
+
+#[derive(Copy, Clone, Debug, ByteEq, Immutable, IntoBytes, ByteHash, FromBytes, Unaligned)]
+#[repr(C)]
+pub(super) struct X25519(pub(crate) [u8; X25519_LEN]);
+
+#[cfg(feature = "x25519")]
+impl From<&x25519_dalek::PublicKey> for OkId {
+    fn from(value: &x25519_dalek::PublicKey) -> Self {
+        Self {
+            hash_type: BinaryType::X25519,
+            digest: Digest::X25519(X25519(*value.as_bytes())),
+        }
+    }
+}
+
+#[cfg(feature = "x25519")]
+impl crate::IntoOkId for &x25519_dalek::PublicKey {}
+
+#[cfg(feature = "x25519")]
+impl TryFrom<OkId> for x25519_dalek::PublicKey {
+    type Error = Error;
+
+    fn try_from(value: OkId) -> Result<Self, Self::Error> {
+        match value.digest {
+            Digest::X25519(key) => Ok(x25519_dalek::PublicKey::from(key.0)),
+            _ => Err(Error::InvalidType),
+        }
+    }
+}
+
+impl Display for X25519 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let buf = &hex::encode(self.0);
+        f.write_str(buf)?;
+        Ok(())
+    }
+}
+
+impl FromStr for X25519 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let buf = hex::decode(s)?;
+        if buf.len() != X25519_LEN {
+            return Err(Error::InvalidLength);
+        }
+        let mut key = [0; X25519_LEN];
+        key.copy_from_slice(&buf);
+        Ok(X25519(key))
+    }
+}
+
+pub(crate) const fn parse_x25519_bytes(bytes: &[u8], start: usize) -> Option<X25519> {
+    let mut result = [0u8; X25519_LEN];
+    let mut i = 0;
+    while i < X25519_LEN * 2 {
+        let high = match hex_to_byte(bytes[start + i]) {
+            Some(b) => b,
+            None => return None,
+        };
+        let low = match hex_to_byte(bytes[start + i + 1]) {
+            Some(b) => b,
+            None => return None,
+        };
+        result[i / 2] = (high << 4) | low;
+        i += 2;
+    }
+    Some(X25519(result))
+}
@@ -0,0 +1,132 @@
+//! X.509 certificate fingerprints and SPKI pins as SHA-256 OkIds.
+//!
+//! Functions taking a certificate accept either DER bytes or a PEM `CERTIFICATE` block.
+//!
+//! # Examples
+//! ```
+//! let pins = okid::x509::parse_pins(
+//!     r#"pin-sha256="d6qzRu9zOECb90Uez27xWltNsj0e1Md7GkYYkVoZWmM="; max-age=5184000"#,
+//! )
+//! .unwrap();
+//! assert_eq!(pins[0].to_hpkp_pin().unwrap(), r#"pin-sha256="d6qzRu9zOECb90Uez27xWltNsj0e1Md7GkYYkVoZWmM=""#);
+//! ```
+
+use std::borrow::Cow;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sha2::Digest as _;
+use x509_cert::{
+    der::{pem, Decode, Encode},
+    spki::ObjectIdentifier,
+    Certificate,
+};
+
+use crate::{BinaryType, Digest, Error, OkId};
+
+const PIN_SHA256: &str = "pin-sha256";
+const ED25519_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+
+/// The SHA-256 fingerprint of a certificate, as shown by browsers and `openssl x509 -fingerprint`.
+pub fn certificate_fingerprint(cert: &[u8]) -> Result<OkId, Error> {
+    Ok(sha256(&certificate_der(cert)?))
+}
+
+/// The SPKI pin of a certificate: SHA-256 over its DER encoded SubjectPublicKeyInfo.
+pub fn spki_pin(cert: &[u8]) -> Result<OkId, Error> {
+    let spki = parse_certificate(cert)?
+        .tbs_certificate
+        .subject_public_key_info
+        .to_der()
+        .map_err(|_| Error::InvalidFormat)?;
+    Ok(sha256(&spki))
+}
+
+/// The SPKI pin of a DER encoded SubjectPublicKeyInfo.
+pub fn spki_pin_from_der(spki: &[u8]) -> OkId {
+    sha256(spki)
+}
+
+/// Extract the Ed25519 public key of a certificate.
+///
+/// Fails with [`Error::InvalidType`] for certificates with any other key algorithm.
+pub fn certificate_public_key(cert: &[u8]) -> Result<OkId, Error> {
+    let spki = parse_certificate(cert)?
+        .tbs_certificate
+        .subject_public_key_info;
+    if spki.algorithm.oid != ED25519_OID {
+        return Err(Error::InvalidType);
+    }
+    let key = spki
+        .subject_public_key
+        .as_bytes()
+        .ok_or(Error::InvalidFormat)?;
+    if key.len() != 32 {
+        return Err(Error::InvalidLength);
+    }
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(key);
+    Ok(OkId {
+        hash_type: BinaryType::PubKey,
+        digest: Digest::PubKey(crate::pub_key::PubKey(buf)),
+    })
+}
+
+/// Parse every `pin-sha256="…"` directive of an HPKP style header value.
+///
+/// Other directives such as `max-age` are ignored.
+pub fn parse_pins(header: &str) -> Result<Vec<OkId>, Error> {
+    header
+        .split(';')
+        .filter_map(|directive| {
+            let (name, value) = directive.split_once('=')?;
+            name.trim()
+                .eq_ignore_ascii_case(PIN_SHA256)
+                .then(|| parse_pin_value(value))
+        })
+        .collect()
+}
+
+fn parse_pin_value(value: &str) -> Result<OkId, Error> {
+    let value = value.trim().trim_matches('"');
+    let bytes = STANDARD.decode(value).map_err(|_| Error::InvalidFormat)?;
+    if bytes.len() != 32 {
+        return Err(Error::InvalidLength);
+    }
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(&bytes);
+    Ok(OkId {
+        hash_type: BinaryType::Sha256,
+        digest: Digest::Sha256(crate::sha2::Sha256(buf)),
+    })
+}
+
+impl OkId {
+    /// Format this SHA-256 OkId as an HPKP `pin-sha256="…"` directive.
+    pub fn to_hpkp_pin(&self) -> Result<String, Error> {
+        match self.digest {
+            Digest::Sha256(sha256) => {
+                Ok(format!("{}=\"{}\"", PIN_SHA256, STANDARD.encode(sha256.0)))
+            }
+            _ => Err(Error::InvalidType),
+        }
+    }
+}
+
+fn sha256(data: &[u8]) -> OkId {
+    sha2::Sha256::new_with_prefix(data).into()
+}
+
+fn certificate_der(cert: &[u8]) -> Result<Cow<'_, [u8]>, Error> {
+    if !cert.trim_ascii_start().starts_with(b"-----BEGIN") {
+        return Ok(Cow::Borrowed(cert));
+    }
+    let (label, der) = pem::decode_vec(cert.trim_ascii()).map_err(|_| Error::InvalidFormat)?;
+    if label != "CERTIFICATE" {
+        return Err(Error::InvalidType);
+    }
+    Ok(Cow::Owned(der))
+}
+
+fn parse_certificate(cert: &[u8]) -> Result<Certificate, Error> {
+    Certificate::from_der(&certificate_der(cert)?).map_err(|_| Error::InvalidFormat)
+}
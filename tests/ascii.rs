@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use okid::{to_ascii, to_ascii_path, to_ascii_sharded, Error, OkId, Sharding};
+
+const HEX: &str = "fcca4276240cd3aa68d8fbb4917e8392c1166a3fcbf7c186b05e4599f38d391a";
+
+fn id() -> OkId {
+    format!("bː{HEX}").parse().unwrap()
+}
+
+#[test]
+fn test_from_ascii() {
+    let uuid = okid::okid!("iː2a4b7c3e0f1d48a9b6c5e4d3f2a1b0c9");
+    for id in [id(), uuid, okid::okid!("fː0011223344556677")] {
+        assert_eq!(OkId::from_ascii(&to_ascii(id)).unwrap(), id);
+    }
+    assert_eq!(to_ascii(id()), format!("1/b/{HEX}"));
+    assert_eq!(to_ascii_sharded(id(), Sharding::default()), to_ascii(id()));
+}
+
+#[test]
+fn test_sharded() {
+    for (sharding, expected) in [
+        (Sharding::new(2, 2), format!("1/b/fc/ca/{HEX}")),
+        (Sharding::new(3, 1), format!("1/b/fcc/{HEX}")),
+        (Sharding::new(0, 4), format!("1/b/{HEX}")),
+        // Shards past the end of the digest are left out
+        (
+            Sharding::new(30, 3),
+            format!("1/b/{}/{}/{HEX}", &HEX[..30], &HEX[30..60]),
+        ),
+    ] {
+        let path = to_ascii_sharded(id(), sharding);
+        assert_eq!(path, expected);
+        assert_eq!(OkId::from_ascii(&path).unwrap(), id());
+        let path = to_ascii_path(id(), sharding);
+        assert_eq!(OkId::from_ascii_path(&path).unwrap(), id());
+    }
+
+    // Any layout parses, as long as the shards are digest prefixes
+    assert_eq!(
+        OkId::from_ascii(&format!("1/b/f/cca/4/{HEX}")).unwrap(),
+        id()
+    );
+}
+
+#[test]
+fn test_invalid() {
+    assert!(matches!(
+        OkId::from_ascii(&format!("2/b/{HEX}")),
+        Err(Error::UnsupportedVersion(v)) if v == "2"
+    ));
+    for (invalid, error) in [
+        (format!("b/{HEX}"), "Invalid format"),
+        (format!("/1/b/{HEX}"), "Invalid format"),
+        ("1/b".to_string(), "Invalid format"),
+        (format!("1/b/ca/{HEX}"), "Invalid format"),
+        (format!("1/b/fc//{HEX}"), "Invalid format"),
+        (format!("1/bb/{HEX}"), "Invalid hash type"),
+        (format!("1/2/{}", &HEX[..40]), "Invalid length"),
+    ] {
+        let e = OkId::from_ascii(&invalid).unwrap_err();
+        assert_eq!(e.to_string(), error, "{invalid}");
+    }
+    assert!(OkId::from_ascii_path(Path::new(&format!("/1/b/{HEX}"))).is_err());
+    assert!(OkId::from_ascii_path(Path::new(&format!("1/b/../b/{HEX}"))).is_err());
+}
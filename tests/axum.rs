@@ -0,0 +1,98 @@
+#![cfg(feature = "axum")]
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use http_body_util::BodyExt;
+use okid::axum::{ETagLayer, OkIdPath};
+use tower::ServiceExt;
+
+const ID: &str = "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+const ENCODED: &str = "2%CB%90b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+const HELLO_ETAG: &str = "\"1/2/b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9\"";
+
+fn app() -> Router {
+    Router::new()
+        .route(
+            "/files/{id}/{name}",
+            get(|OkIdPath(id): OkIdPath| async move { id.to_string() }),
+        )
+        .route("/hello", get(|| async { "hello world" }))
+        .route(
+            "/tagged",
+            get(|| async { ([(header::ETAG, "\"v1\"")], "hello world") }),
+        )
+        .layer(ETagLayer::new())
+}
+
+async fn send(uri: &str, if_none_match: Option<&str>) -> (StatusCode, Option<String>, String) {
+    let mut request = Request::get(uri);
+    if let Some(if_none_match) = if_none_match {
+        request = request.header(header::IF_NONE_MATCH, if_none_match);
+    }
+    let response = app()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let etag = response
+        .headers()
+        .get(header::ETAG)
+        .map(|etag| etag.to_str().unwrap().to_string());
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, etag, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_okid_path() {
+    let (status, _, body) = send(&format!("/files/{ENCODED}/hello.txt"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, ID);
+
+    // The rejection names why the id-looking segment failed
+    let (status, _, body) = send("/files/2%CB%90b94d/hello.txt", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body, "Invalid OkId in path: Invalid length");
+
+    let (status, _, body) = send("/files/readme/hello.txt", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body, "Invalid OkId in path: No okid found");
+}
+
+#[tokio::test]
+async fn test_etag_layer() {
+    let (status, etag, body) = send("/hello", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some(HELLO_ETAG));
+    assert_eq!(body, "hello world");
+
+    // Weak and display-form tags match too
+    for if_none_match in [
+        HELLO_ETAG.to_string(),
+        format!("\"other\", W/{HELLO_ETAG}"),
+        format!("\"{ID}\""),
+        "*".to_string(),
+    ] {
+        let (status, etag, body) = send("/hello", Some(&if_none_match)).await;
+        assert_eq!(status, StatusCode::NOT_MODIFIED, "{if_none_match}");
+        assert_eq!(etag.as_deref(), Some(HELLO_ETAG));
+        assert!(body.is_empty());
+    }
+
+    let stale = format!("\"1/2/{}\"", "00".repeat(32));
+    let (status, _, body) = send("/hello", Some(&stale)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "hello world");
+
+    // Existing tags that are not OkIds are left alone
+    let (status, etag, _) = send("/tagged", Some("\"v1\"")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"v1\""));
+
+    // Errors are not tagged
+    let (status, etag, _) = send("/files/readme/x", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(etag.is_none());
+}
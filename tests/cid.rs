@@ -0,0 +1,77 @@
+#![cfg(all(feature = "cid", feature = "blake3", feature = "sha3"))]
+use cid::{multibase::Base, Cid};
+use okid::{Error, OkId};
+
+const SHA256: &str = "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+const PUB_KEY: &str = "pːd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+
+#[test]
+fn test_multihash_roundtrip() {
+    let id: OkId = SHA256.parse().unwrap();
+    let multihash = id.to_multihash();
+    assert_eq!(&multihash[..2], &[0x12, 0x20]);
+    assert_eq!(OkId::from_multihash(&multihash).unwrap(), id);
+
+    let blake3 = OkId::from(blake3::Hasher::new());
+    assert_eq!(blake3.to_multihash()[0], 0x1e);
+    assert_eq!(
+        OkId::from_multihash(&blake3.to_multihash()).unwrap(),
+        blake3
+    );
+
+    let sha3 = OkId::from(sha3::Sha3_512::default());
+    assert_eq!(sha3.to_multihash()[0], 0x14);
+    assert_eq!(OkId::from_multihash(&sha3.to_multihash()).unwrap(), sha3);
+}
+
+#[test]
+fn test_cid_codecs_and_bases() {
+    let id: OkId = SHA256.parse().unwrap();
+    let cid = Cid::from(id);
+    assert_eq!(cid.codec(), okid::cid::RAW);
+    assert_eq!(
+        cid.to_string(),
+        id.to_cid_string(okid::cid::RAW, Base::Base32Lower).unwrap()
+    );
+
+    let dag_cbor = id.to_cid(okid::cid::DAG_CBOR);
+    assert_eq!(dag_cbor.codec(), 0x71);
+    assert_eq!(OkId::try_from(&dag_cbor).unwrap(), id);
+
+    let base58 = id
+        .to_cid_string(okid::cid::DAG_PB, Base::Base58Btc)
+        .unwrap();
+    assert!(base58.starts_with('z'));
+    assert_eq!(OkId::from_cid_str(&base58).unwrap(), id);
+    assert!(OkId::from_cid_str("bnotacid").is_err());
+}
+
+#[test]
+fn test_non_hash_kinds() {
+    // Public keys are embedded with the identity multihash
+    let key: OkId = PUB_KEY.parse().unwrap();
+    let cid = Cid::from(key);
+    assert_eq!(cid.hash().code(), 0x00);
+    assert_eq!(OkId::try_from(&cid).unwrap(), key);
+
+    // Signatures don't fit, so they are hashed
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]);
+    let signature = okid::signature::sign(&signing_key, b"hello");
+    let cid = Cid::from(signature);
+    assert_eq!(cid.hash().code(), 0x12);
+    assert_ne!(OkId::try_from(&cid).unwrap(), signature);
+}
+
+#[test]
+fn test_unsupported_multihash() {
+    let sha2_512 = cid::multihash::Multihash::<64>::wrap(0x13, &[0u8; 64]).unwrap();
+    let cid = Cid::new_v1(okid::cid::RAW, sha2_512);
+    match OkId::try_from(&cid) {
+        Err(Error::UnsupportedAlgorithm(name)) => assert_eq!(name, "sha2-512"),
+        other => panic!("expected unsupported algorithm, got {other:?}"),
+    }
+
+    let unknown = cid::multihash::Multihash::<64>::wrap(0x3000, &[0u8; 32]).unwrap();
+    let err = OkId::from_multihash(&unknown.to_bytes()).unwrap_err();
+    assert_eq!(err.to_string(), "Unsupported algorithm: multihash 0x3000");
+}
@@ -0,0 +1,69 @@
+#![cfg(any(feature = "sha2", feature = "blake3"))]
+use okid::{
+    commitment::{self, CommitmentAlgorithm, Opening},
+    OkId,
+};
+
+#[test]
+fn test_commit_and_verify() {
+    let (commitment, opening) = commitment::commit(b"sealed bid: 1000").unwrap();
+    assert!(commitment::verify(
+        &commitment,
+        b"sealed bid: 1000",
+        &opening
+    ));
+    assert!(!commitment::verify(
+        &commitment,
+        b"sealed bid: 1001",
+        &opening
+    ));
+
+    let other = Opening::random().unwrap();
+    assert!(!commitment::verify(
+        &commitment,
+        b"sealed bid: 1000",
+        &other
+    ));
+}
+
+#[test]
+fn test_commitment_is_hiding() {
+    // Committing to the same data twice yields unrelated commitments
+    let (a, _) = commitment::commit(b"hello world").unwrap();
+    let (b, _) = commitment::commit(b"hello world").unwrap();
+    assert_ne!(a, b);
+}
+
+#[cfg(feature = "sha2")]
+#[test]
+fn test_commitment_is_domain_separated() {
+    use sha2::Digest;
+    let opening = Opening::from_bytes([0u8; 32]);
+    let commitment =
+        OkId::commit_with_opening(CommitmentAlgorithm::Sha256, b"hello world", &opening);
+
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(opening.as_bytes());
+    hasher.update(b"hello world");
+    assert_ne!(commitment, OkId::from(hasher));
+    insta::assert_snapshot!(commitment.to_string(), @"2ːbcf8dff0c1645b0dba9ece82421a69bc54df0af02b87caeaf1d5f198b7bd6567");
+}
+
+#[test]
+fn test_opening_roundtrip() {
+    use jetstream_wireformat::WireFormat;
+
+    let opening = Opening::random().unwrap();
+
+    let mut buf = vec![];
+    opening.encode(&mut buf).unwrap();
+    assert_eq!(opening.byte_size(), buf.len() as u32);
+    assert_eq!(opening, Opening::decode(&mut buf.as_slice()).unwrap());
+
+    assert_eq!(opening, opening.to_string().parse().unwrap());
+    #[cfg(feature = "json")]
+    {
+        let json = serde_json::to_string(&opening).unwrap();
+        assert_eq!(opening, serde_json::from_str::<Opening>(&json).unwrap());
+    }
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "did")]
+use okid::OkId;
+
+// did:key spec test vectors
+const ED25519: &str = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+const X25519: &str = "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F";
+const SECP256K1: &str = "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme";
+const P256: &str = "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169";
+
+#[test]
+fn test_did_key_roundtrip() {
+    for (did, kind) in [(ED25519, 'p'), (X25519, 'x'), (SECP256K1, 'e'), (P256, 'r')] {
+        let id = OkId::from_did_key(did).unwrap();
+        assert!(id.to_string().starts_with(kind), "{did} parsed as {id}");
+        assert_eq!(id.to_did_key().unwrap(), did);
+    }
+}
+
+#[test]
+fn test_did_key_matches_verifying_key() {
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let id = OkId::from(&signing_key.verifying_key());
+    let did = id.to_did_key().unwrap();
+    assert!(did.starts_with("did:key:z6Mk"));
+    assert_eq!(
+        OkId::from_did_key(&format!("{did}#{}", id.to_multikey().unwrap())).unwrap(),
+        id
+    );
+}
+
+#[test]
+fn test_did_key_rejects_unsupported() {
+    let fingerprint = OkId::from(42u64);
+    assert!(fingerprint.to_did_key().is_err());
+    assert!(OkId::from_did_key("did:web:example.com").is_err());
+    // multibase base32 is not a did:key encoding
+    assert!(OkId::from_did_key("did:key:b6mk").is_err());
+}
@@ -0,0 +1,114 @@
+#![cfg(all(feature = "digest-set", feature = "json"))]
+use okid::{
+    digest_set::{Algorithm, DigestSet, ResourceDescriptor},
+    Error, OkId,
+};
+use serde::{Deserialize, Serialize};
+
+const ARTIFACT: &[u8] = b"hello world";
+const SHA256: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+const SHA512: &str = "309ecc489c12d6eb4cc40f50c902f2b4d0ed77ee511a7c7a9bcd3ca86d4cd86f989dd35bc5ff499670da34255b45b0cfd830e81f605dcf7dc5542e93ae9cd76f";
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SpdxFile {
+    #[serde(rename = "fileName")]
+    file_name: String,
+    #[serde(with = "okid::digest_set::spdx")]
+    checksums: DigestSet,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Component {
+    name: String,
+    #[serde(with = "okid::digest_set::cyclonedx")]
+    hashes: DigestSet,
+}
+
+fn hello() -> DigestSet {
+    let mut set = DigestSet::from_reader(ARTIFACT, &[Algorithm::Sha256]).unwrap();
+    set.insert_hex("sha512", SHA512).unwrap();
+    set
+}
+
+#[test]
+fn test_in_toto() {
+    let json =
+        format!(r#"{{"name":"hello.txt","digest":{{"sha256":"{SHA256}","sha512":"{SHA512}"}}}}"#);
+    let descriptor: ResourceDescriptor = serde_json::from_str(&json).unwrap();
+    assert_eq!(descriptor.name.as_deref(), Some("hello.txt"));
+    assert_eq!(descriptor.digest, hello());
+    assert_eq!(
+        descriptor.digest.get("sha256").unwrap(),
+        format!("2ː{SHA256}").parse::<OkId>().unwrap()
+    );
+    assert_eq!(
+        descriptor.digest.other().collect::<Vec<_>>(),
+        vec![("sha512", SHA512)]
+    );
+    assert_eq!(serde_json::to_string(&descriptor).unwrap(), json);
+
+    // Known algorithms must decode to an OkId of the right length
+    let bad = json.replace(SHA256, &SHA256[2..]);
+    assert!(serde_json::from_str::<ResourceDescriptor>(&bad).is_err());
+}
+
+#[test]
+fn test_spdx() {
+    let json = format!(
+        r#"{{"fileName":"./hello.txt","checksums":[{{"algorithm":"SHA256","checksumValue":"{SHA256}"}},{{"algorithm":"SHA512","checksumValue":"{SHA512}"}}]}}"#
+    );
+    let file: SpdxFile = serde_json::from_str(&json).unwrap();
+    assert_eq!(file.checksums, hello());
+    assert_eq!(serde_json::to_string(&file).unwrap(), json);
+}
+
+#[test]
+fn test_cyclonedx() {
+    let json = format!(
+        r#"{{"name":"hello","hashes":[{{"alg":"SHA-256","content":"{SHA256}"}},{{"alg":"SHA-512","content":"{SHA512}"}}]}}"#
+    );
+    let component: Component = serde_json::from_str(&json).unwrap();
+    assert_eq!(component.hashes, hello());
+    assert_eq!(serde_json::to_string(&component).unwrap(), json);
+
+    // The same set converts between formats
+    let spdx = serde_json::to_value(SpdxFile {
+        file_name: "hello".to_string(),
+        checksums: component.hashes,
+    })
+    .unwrap();
+    assert_eq!(spdx["checksums"][1]["algorithm"], "SHA512");
+}
+
+#[test]
+fn test_verify() {
+    let set = hello();
+    set.verify(ARTIFACT).unwrap();
+
+    let err = set.verify(&b"hello WORLD"[..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    let inner = err.into_inner().unwrap().downcast::<Error>().unwrap();
+    assert!(matches!(*inner, Error::DigestMismatch { .. }));
+
+    // Every entry with an OkId kind is checked
+    #[cfg(feature = "blake3")]
+    {
+        let mut set = set.clone();
+        set.insert(OkId::from(blake3::Hasher::new())).unwrap();
+        assert!(set.verify(ARTIFACT).is_err());
+    }
+
+    // Only entries without an OkId kind: nothing can be checked
+    let mut only512 = DigestSet::new();
+    only512.insert_hex("sha512", SHA512).unwrap();
+    let err = only512.verify(ARTIFACT).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    let key: OkId = "pːd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        .parse()
+        .unwrap();
+    assert!(matches!(
+        DigestSet::new().insert(key),
+        Err(Error::InvalidType)
+    ));
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "git")]
+use okid::{
+    git::{blob_id, commit_id, parse_oid, write_tree, Mode, ObjectFormat, TreeBuilder},
+    OkId,
+};
+
+const EMPTY_TREE_SHA1: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+const EMPTY_TREE_SHA256: &str = "6ef19b41225c5369f1c104d45d8d85efa9b057b53b14b4b9b939dd74decc5321";
+
+#[test]
+fn test_blob_ids() {
+    for (format, empty, hello) in [
+        (
+            ObjectFormat::Sha1,
+            "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391",
+            "3b18e512dba79e4c8300dd08aeb37f8e728b8dad",
+        ),
+        (
+            ObjectFormat::Sha256,
+            "473a0f4c3be8a93681a267e3b1e9a7dcda1185436fe141f7749120a303721813",
+            "0bd69098bd9b9cc5934a610ab65da429b525361147faa7b5b922919e9a23143d",
+        ),
+    ] {
+        assert_eq!(blob_id(format, b"").to_git_oid().unwrap(), empty);
+        assert_eq!(
+            blob_id(format, b"hello world\n").to_git_oid().unwrap(),
+            hello
+        );
+        assert_eq!(parse_oid(hello).unwrap(), blob_id(format, b"hello world\n"));
+        assert_eq!(
+            ObjectFormat::of(&parse_oid(hello).unwrap()).unwrap(),
+            format
+        );
+    }
+}
+
+#[test]
+fn test_parse_oid() {
+    assert!(parse_oid("3b18e512").is_err());
+    assert!(parse_oid("zb18e512dba79e4c8300dd08aeb37f8e728b8dad").is_err());
+    let key: OkId = "pːd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        .parse()
+        .unwrap();
+    assert!(key.to_git_oid().is_err());
+}
+
+#[test]
+fn test_tree_builder() {
+    assert_eq!(
+        TreeBuilder::new(ObjectFormat::Sha1)
+            .id()
+            .to_git_oid()
+            .unwrap(),
+        EMPTY_TREE_SHA1
+    );
+    assert_eq!(
+        TreeBuilder::new(ObjectFormat::Sha256)
+            .id()
+            .to_git_oid()
+            .unwrap(),
+        EMPTY_TREE_SHA256
+    );
+
+    let mut tree = TreeBuilder::new(ObjectFormat::Sha1);
+    let blob = blob_id(ObjectFormat::Sha1, b"x");
+    assert!(tree.insert("a/b", Mode::File, blob).is_err());
+    assert!(tree.insert("..", Mode::File, blob).is_err());
+    assert!(tree
+        .insert("x", Mode::File, blob_id(ObjectFormat::Sha256, b"x"))
+        .is_err());
+
+    // `sub` sorts after `sub.txt` because trees compare as `sub/`
+    let sub = {
+        let mut sub = TreeBuilder::new(ObjectFormat::Sha1);
+        sub.insert("f", Mode::File, blob).unwrap();
+        sub.id()
+    };
+    tree.insert("sub", Mode::Tree, sub).unwrap();
+    tree.insert("sub.txt", Mode::File, blob).unwrap();
+    let bytes = tree.to_bytes();
+    let txt = bytes.windows(7).position(|w| w == b"sub.txt").unwrap();
+    let dir = bytes.windows(4).position(|w| w == b"sub\0").unwrap();
+    assert!(txt < dir);
+}
+
+#[test]
+fn test_commit_id() {
+    let contents = "tree 5cac1d9871deb286a4003608964212ef16959a08\n\
+                    author A <a@example.com> 0 +0000\n\
+                    committer A <a@example.com> 0 +0000\n\
+                    \n\
+                    msg\n";
+    assert_eq!(
+        commit_id(ObjectFormat::Sha1, contents.as_bytes())
+            .to_git_oid()
+            .unwrap(),
+        "64f4671236038338bd50887cf74dd0f4e02e78aa"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_tree_matches_git() {
+    use std::{fs, os::unix::fs::PermissionsExt};
+
+    let root = std::env::temp_dir().join(format!("okid-git-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("sub/deeper")).unwrap();
+    fs::create_dir_all(root.join("empty")).unwrap();
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::write(root.join(".git/HEAD"), "ignored").unwrap();
+    fs::write(root.join("a.txt"), "hello world\n").unwrap();
+    fs::write(root.join("run.sh"), "#!/bin/sh\n").unwrap();
+    fs::set_permissions(root.join("run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+    fs::write(root.join("sub/deeper/f"), "x").unwrap();
+    fs::write(root.join("sub.txt"), "y").unwrap();
+    std::os::unix::fs::symlink("a.txt", root.join("link")).unwrap();
+
+    // Ids from `git add -A && git write-tree` over the same layout
+    let sha1 = write_tree(&root, ObjectFormat::Sha1).unwrap();
+    let sha256 = write_tree(&root, ObjectFormat::Sha256).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+    assert_eq!(
+        sha1.to_git_oid().unwrap(),
+        "5cac1d9871deb286a4003608964212ef16959a08"
+    );
+    assert_eq!(
+        sha256.to_git_oid().unwrap(),
+        "979ca0c12317485bac3ecd79dc00e1e84462f8f6ae37fe8cc016ee89aeebb923"
+    );
+}
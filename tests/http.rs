@@ -0,0 +1,153 @@
+#![cfg(feature = "http")]
+use headers::{ETag, HeaderMap, HeaderMapExt, HeaderValue};
+use okid::{
+    http::{ContentDigest, IfNoneMatch, ReprDigest, WantContentDigest},
+    Error, OkId,
+};
+
+// The examples of RFC 9530
+const BODY: &[u8] = b"{\"hello\": \"world\"}";
+const SHA256: &str = "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:";
+const SHA512: &str = "sha-512=:WZDPaVn/7XgHaAy8pmojAkGWoRx2UFChF41A2svX+TaPm+AbwAgBWnrIiYllu7BNNyealdVLvRwEmTHWXvJwew==:";
+
+fn headers(name: &'static str, values: &[&str]) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for value in values {
+        map.append(name, HeaderValue::from_str(value).unwrap());
+    }
+    map
+}
+
+#[test]
+fn test_content_digest() {
+    let digest = headers("content-digest", &[&format!("{SHA512}, {SHA256}")])
+        .typed_get::<ContentDigest>()
+        .unwrap();
+    assert_eq!(digest, ContentDigest::from_body(BODY));
+    assert_eq!(
+        digest.ids()[0].to_string(),
+        "2ː5f8f04f6a3a892aaabbddb6cf273894493773960d4a325b105fee46eef4304f1"
+    );
+    digest.verify(BODY).unwrap();
+    assert!(matches!(
+        digest.verify(b"{}"),
+        Err(Error::DigestMismatch { .. })
+    ));
+
+    let mut map = HeaderMap::new();
+    map.typed_insert(digest);
+    assert_eq!(map["content-digest"], SHA256);
+}
+
+#[test]
+fn test_repr_digest_structured_fields() {
+    // Parameters, other item kinds and multiple field lines are all structured fields
+    let repr = headers(
+        "repr-digest",
+        &[
+            "unixsum=30637;a=\"x,y\", md5=?1",
+            &format!("{SHA256};p=1, crc32c=(1 :AA==: \"s\")"),
+        ],
+    )
+    .typed_get::<ReprDigest>()
+    .unwrap();
+    assert_eq!(repr.ids(), ContentDigest::from_body(BODY).ids());
+
+    // Unpadded byte sequences are accepted
+    let unpadded = SHA256.replace("=:", ":").replacen(":", "=:", 1);
+    assert!(headers("repr-digest", &[&unpadded])
+        .typed_get::<ReprDigest>()
+        .is_some());
+
+    for invalid in [
+        SHA512,
+        "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=",
+        "sha-256=:AAAA:",
+        "sha-256=1",
+        "SHA-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:",
+        "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:,",
+    ] {
+        assert!(
+            headers("repr-digest", &[invalid])
+                .typed_get::<ReprDigest>()
+                .is_none(),
+            "{invalid:?} should be rejected"
+        );
+    }
+
+    let key: OkId = "pːd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        .parse()
+        .unwrap();
+    assert!(matches!(ReprDigest::new(key), Err(Error::InvalidType)));
+}
+
+#[test]
+fn test_want_content_digest() {
+    let want = headers("want-content-digest", &["sha-512=3, sha-256=10, unixsum=0"])
+        .typed_get::<WantContentDigest>()
+        .unwrap();
+    assert_eq!(want.preference("sha-512"), Some(3));
+    assert!(want.accepts_sha256());
+
+    let refuses = WantContentDigest::new([("sha-256", 0), ("sha-512", 10)]).unwrap();
+    assert!(!refuses.accepts_sha256());
+    let mut map = HeaderMap::new();
+    map.typed_insert(refuses);
+    assert_eq!(map["want-content-digest"], "sha-256=0, sha-512=10");
+
+    assert!(WantContentDigest::new([("sha-256", 11)]).is_err());
+    assert!(headers("want-content-digest", &["sha-256=11"])
+        .typed_get::<WantContentDigest>()
+        .is_none());
+}
+
+#[test]
+fn test_etag() {
+    let id = ContentDigest::from_body(BODY).ids()[0];
+    let etag = ETag::from(id);
+    assert!(!etag.is_weak());
+    assert_eq!(OkId::try_from(&etag).unwrap(), id);
+
+    let mut map = HeaderMap::new();
+    map.typed_insert(etag);
+    assert_eq!(
+        map["etag"],
+        "\"1/2/5f8f04f6a3a892aaabbddb6cf273894493773960d4a325b105fee46eef4304f1\""
+    );
+}
+
+#[test]
+fn test_if_none_match() {
+    let id = ContentDigest::from_body(BODY).ids()[0];
+    let other = ContentDigest::from_body(b"{}").ids()[0];
+
+    // Weak tags, the display form and foreign tags in one list
+    let list = format!(r#"W/"1/2/{}", "xyzzy", "{}", "a,b""#, hex(&id), other);
+    let if_none_match = headers("if-none-match", &[&list])
+        .typed_get::<IfNoneMatch>()
+        .unwrap();
+    assert!(if_none_match.matches(&id));
+    assert!(if_none_match.matches(&other));
+    assert!(!if_none_match.matches(&ContentDigest::from_body(b"").ids()[0]));
+
+    let any = headers("if-none-match", &["*"])
+        .typed_get::<IfNoneMatch>()
+        .unwrap();
+    assert_eq!(any, IfNoneMatch::Any);
+    assert!(any.matches(&id));
+
+    assert!(headers("if-none-match", &["1/2/abc"])
+        .typed_get::<IfNoneMatch>()
+        .is_none());
+
+    let mut map = HeaderMap::new();
+    map.typed_insert(IfNoneMatch::Ids(vec![id]));
+    assert_eq!(
+        map.typed_get::<IfNoneMatch>().unwrap(),
+        IfNoneMatch::Ids(vec![id])
+    );
+}
+
+fn hex(id: &OkId) -> String {
+    id.to_string().split_once('ː').unwrap().1.to_string()
+}
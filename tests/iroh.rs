@@ -0,0 +1,44 @@
+#![cfg(feature = "iroh")]
+use okid::OkId;
+
+const CONTENT: &str = "bːaf1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262";
+
+fn node() -> OkId {
+    OkId::from(&ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]).verifying_key())
+}
+
+#[test]
+fn test_hash_roundtrip() {
+    let hash = iroh_blobs::Hash::new(b"");
+    let id = OkId::from(hash);
+    assert_eq!(id.to_string(), CONTENT);
+    assert_eq!(iroh_blobs::Hash::try_from(id).unwrap(), hash);
+
+    let raw = iroh_blobs::HashAndFormat::try_from(id).unwrap();
+    assert_eq!(raw, iroh_blobs::HashAndFormat::raw(hash));
+    assert_eq!(OkId::from(iroh_blobs::HashAndFormat::hash_seq(hash)), id);
+
+    let sha256: OkId = "2ːe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        .parse()
+        .unwrap();
+    assert!(iroh_blobs::Hash::try_from(sha256).is_err());
+}
+
+#[test]
+fn test_blob_ticket_roundtrip() {
+    let content: OkId = CONTENT.parse().unwrap();
+    let ticket = okid::iroh::blob_ticket(&node(), &content).unwrap();
+    assert_eq!(ticket.format(), iroh_blobs::BlobFormat::Raw);
+    assert_eq!(okid::iroh::blob_ticket_ids(&ticket), (node(), content));
+
+    let s = ticket.to_string();
+    assert!(s.starts_with("blob"));
+    assert_eq!(
+        okid::iroh::parse_blob_ticket(&s).unwrap(),
+        (node(), content)
+    );
+
+    // Arguments in the wrong order
+    assert!(okid::iroh::blob_ticket(&content, &node()).is_err());
+    assert!(okid::iroh::parse_blob_ticket("blobnotaticket").is_err());
+}
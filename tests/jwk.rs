@@ -0,0 +1,64 @@
+#![cfg(feature = "jwk")]
+use okid::{
+    jwk::{Jwk, Jwks},
+    OkId,
+};
+
+// RFC 8037 appendix A
+const PUBLIC: &str = "pːd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+const X: &str = "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo";
+const THUMBPRINT: &str = "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k";
+
+#[test]
+fn test_ed25519_jwk_thumbprint() {
+    use base64::Engine;
+    let key: OkId = PUBLIC.parse().unwrap();
+    let jwk = key.to_jwk().unwrap();
+    assert_eq!(
+        (jwk.kty.as_str(), jwk.crv.as_str(), jwk.x.as_str()),
+        ("OKP", "Ed25519", X)
+    );
+
+    let thumbprint = key.jwk_thumbprint().unwrap();
+    let expected = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(THUMBPRINT)
+        .unwrap();
+    assert_eq!(
+        thumbprint.to_string(),
+        format!("2ː{}", hex::encode(expected))
+    );
+}
+
+#[test]
+fn test_rsa_jwk_thumbprint() {
+    // RFC 7638 section 3.1, NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs
+    let jwk = Jwk {
+        kty: "RSA".to_string(),
+        n: Some("0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw".to_string()),
+        e: Some("AQAB".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        jwk.thumbprint().unwrap().to_string(),
+        "2ː3736cbb1787cb8309c77ee8c3705c5e16ffb9e859715901f1e4c59b11182f57b"
+    );
+}
+
+#[test]
+fn test_jwks_keyed_by_thumbprint() {
+    let key: OkId = PUBLIC.parse().unwrap();
+    let jwks = Jwks::from_keys([key]).unwrap();
+    let jwk = jwks.get(&key.jwk_thumbprint().unwrap()).unwrap();
+    assert_eq!(OkId::try_from(jwk).unwrap(), key);
+    assert!(Jwks::from_keys([OkId::from(1u64)]).is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_jwk_json_roundtrip() {
+    let key: OkId = PUBLIC.parse().unwrap();
+    let json = key.to_jwk_json().unwrap();
+    assert!(json.contains(&format!("\"kid\":\"{}\"", key.jwk_thumbprint().unwrap())));
+    assert_eq!(OkId::from_jwk_json(&json).unwrap(), key);
+    assert!(OkId::from_jwk_json(r#"{"kty":"EC","crv":"P-256","x":"AA","y":"AA"}"#).is_err());
+}
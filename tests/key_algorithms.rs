@@ -0,0 +1,79 @@
+use jetstream_wireformat::WireFormat;
+use okid::OkId;
+
+const X25519: &str = "xː8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a";
+const SECP256K1: &str = "eː0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+const P256: &str = "rː036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296";
+
+#[test]
+fn test_key_kinds_parse_and_wireformat() {
+    for s in [X25519, SECP256K1, P256] {
+        let id: OkId = s.parse().unwrap();
+        assert_eq!(id.to_string(), s);
+        assert_eq!(okid::const_parse_okid(s), Some(id));
+
+        let mut buf = vec![];
+        id.encode(&mut buf).unwrap();
+        assert_eq!(id.byte_size(), buf.len() as u32);
+        assert_eq!(id.encoded_size(), buf.len());
+        assert_eq!(OkId::decode(&mut buf.as_slice()).unwrap(), id);
+    }
+
+    // Key kinds are distinct even when the bytes are the same length
+    assert!(X25519.replacen('x', "p", 1).parse::<OkId>().unwrap() != X25519.parse().unwrap());
+    assert!("eː0279be".parse::<OkId>().is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_key_kinds_serde() {
+    let ed25519: OkId = "pːd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        .parse()
+        .unwrap();
+    for id in [X25519, SECP256K1, P256]
+        .iter()
+        .map(|s| s.parse::<OkId>().unwrap())
+        .chain([ed25519])
+    {
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(serde_json::from_str::<OkId>(&json).unwrap(), id);
+    }
+}
+
+#[cfg(feature = "x25519")]
+#[test]
+fn test_x25519_roundtrip() {
+    // RFC 7748 section 6.1, Alice's public key
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(&X25519["xː".len()..], &mut bytes).unwrap();
+    let public = x25519_dalek::PublicKey::from(bytes);
+    let id = OkId::from(&public);
+    assert_eq!(id.to_string(), X25519);
+    assert_eq!(x25519_dalek::PublicKey::try_from(id).unwrap(), public);
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn test_secp256k1_roundtrip() {
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let signing_key = k256::ecdsa::SigningKey::from_slice(&one).unwrap();
+    let id = OkId::from(signing_key.verifying_key());
+    assert_eq!(id.to_string(), SECP256K1);
+    assert_eq!(
+        k256::ecdsa::VerifyingKey::try_from(id).unwrap(),
+        *signing_key.verifying_key()
+    );
+    assert!(ed25519_dalek::VerifyingKey::try_from(id).is_err());
+}
+
+#[cfg(feature = "p256")]
+#[test]
+fn test_p256_roundtrip() {
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let public = p256::SecretKey::from_slice(&one).unwrap().public_key();
+    let id = OkId::from(&public);
+    assert_eq!(id.to_string(), P256);
+    assert_eq!(p256::PublicKey::try_from(id).unwrap(), public);
+}
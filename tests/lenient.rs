@@ -0,0 +1,114 @@
+use okid::{
+    lenient::{Form, LenientOptions},
+    Error, OkId,
+};
+
+const ID: &str = "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+const HEX: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+fn id() -> OkId {
+    ID.parse().unwrap()
+}
+
+#[test]
+fn test_forms() {
+    let options = LenientOptions::default();
+    for (input, form, trimmed, lowercased) in [
+        (ID.to_string(), Form::Display, false, false),
+        (format!("2:{HEX}"), Form::AsciiSeparator, false, false),
+        (format!("1/2/{HEX}"), Form::Ascii, false, false),
+        (format!("1/2/b9/4d/{HEX}"), Form::Ascii, false, false),
+        (id().to_display_safe(), Form::DisplaySafe, false, false),
+        (id().to_bubblebabble(), Form::Bubblebabble, false, false),
+        (format!("  '{ID}'\n"), Form::Display, true, false),
+        // Hex is case-insensitive even in the display form
+        (
+            format!("`2:{}`", HEX.to_uppercase()),
+            Form::AsciiSeparator,
+            true,
+            false,
+        ),
+        (
+            format!("\" 1/2/{} \"", HEX.to_uppercase()),
+            Form::Ascii,
+            true,
+            false,
+        ),
+        (
+            format!(" {} ", id().to_display_safe()),
+            Form::DisplaySafe,
+            true,
+            false,
+        ),
+    ] {
+        let parsed = OkId::parse_lenient(&input, &options).unwrap();
+        assert_eq!(parsed.id, id(), "{input:?}");
+        assert_eq!(parsed.form, form, "{input:?}");
+        assert_eq!(parsed.trimmed, trimmed, "{input:?}");
+        assert_eq!(parsed.lowercased, lowercased, "{input:?}");
+    }
+
+    let uuid = okid::okid!("iː2a4b7c3e0f1d48a9b6c5e4d3f2a1b0c9");
+    for (input, form) in [
+        ("Iː2A4B7C3E0F1D48A9B6C5E4D3F2A1B0C9", Form::Display),
+        ("I:2a4b7c3e0f1d48a9b6c5e4d3f2a1b0c9", Form::AsciiSeparator),
+        ("1/I/2a4b7c3e0f1d48a9b6c5e4d3f2a1b0c9", Form::Ascii),
+    ] {
+        let parsed = OkId::parse_lenient(input, &options).unwrap();
+        assert_eq!(
+            (parsed.id, parsed.form, parsed.lowercased),
+            (uuid, form, true)
+        );
+    }
+
+    // FromStr stays strict
+    assert!(format!("2:{HEX}").parse::<OkId>().is_err());
+    assert!(format!(" {ID}").parse::<OkId>().is_err());
+}
+
+#[test]
+fn test_bubblebabble() {
+    // Odd and even lengths, and the checksum catches a changed word
+    for id in [id(), okid::okid!("fː0123456789abcdef")] {
+        let babble = id.to_bubblebabble();
+        let parsed = OkId::parse_lenient(&babble, &LenientOptions::default()).unwrap();
+        assert_eq!(parsed.id, id);
+    }
+    let babble = id().to_bubblebabble();
+    let mut tampered = babble.clone().into_bytes();
+    tampered[1] = if tampered[1] == b'a' { b'e' } else { b'a' };
+    let tampered = String::from_utf8(tampered).unwrap();
+    assert!(OkId::parse_lenient(&tampered, &LenientOptions::default()).is_err());
+    assert!(OkId::parse_lenient("xx", &LenientOptions::default()).is_err());
+}
+
+#[test]
+fn test_options() {
+    let strict = LenientOptions::strict();
+    assert_eq!(
+        OkId::parse_lenient(ID, &strict).unwrap().form,
+        Form::Display
+    );
+    for input in [
+        format!("2:{HEX}"),
+        format!("1/2/{HEX}"),
+        format!(" {ID} "),
+        "Iː2a4b7c3e0f1d48a9b6c5e4d3f2a1b0c9".to_string(),
+        id().to_display_safe(),
+        id().to_bubblebabble(),
+    ] {
+        assert!(OkId::parse_lenient(&input, &strict).is_err(), "{input:?}");
+    }
+
+    let no_case = LenientOptions {
+        uppercase: false,
+        ..LenientOptions::default()
+    };
+    assert!(OkId::parse_lenient("I:2a4b7c3e0f1d48a9b6c5e4d3f2a1b0c9", &no_case).is_err());
+
+    // The error is that of the display form
+    assert!(matches!(
+        OkId::parse_lenient("2ːb94d", &LenientOptions::default()),
+        Err(Error::InvalidLength)
+    ));
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "ni")]
+use okid::{
+    ni::{Algorithm, NamedInfo},
+    Error, OkId,
+};
+
+// "Hello World!", from RFC 6920 section 8
+const HELLO: &str = "2ː7f83b1657ff1fc53b92dc18148a1d65dfc2d4b1fa3d677284addd200126d9069";
+const NI: &str = "ni:///sha-256;f4OxZX_x_FO5LcGBSKHWXfwtSx-j1ncoSt3SABJtkGk";
+
+#[test]
+fn test_ni_roundtrip() {
+    let id: OkId = HELLO.parse().unwrap();
+    assert_eq!(id.to_ni_uri("").unwrap(), NI);
+    assert_eq!(OkId::from_ni_uri(NI).unwrap(), id);
+
+    let with_authority = id.to_ni_uri("example.com").unwrap();
+    assert_eq!(
+        with_authority,
+        "ni://example.com/sha-256;f4OxZX_x_FO5LcGBSKHWXfwtSx-j1ncoSt3SABJtkGk"
+    );
+    let name: NamedInfo = format!("{with_authority}?ct=text/plain").parse().unwrap();
+    assert_eq!(name.authority.as_deref(), Some("example.com"));
+    assert_eq!(OkId::try_from(&name).unwrap(), id);
+
+    let url = url::Url::parse(&with_authority).unwrap();
+    assert_eq!(OkId::try_from(&url).unwrap(), id);
+}
+
+#[test]
+fn test_truncated() {
+    let id: OkId = HELLO.parse().unwrap();
+    let name: NamedInfo = "ni:///sha-256-128;f4OxZX_x_FO5LcGBSKHWXQ".parse().unwrap();
+    assert_eq!(name.algorithm, Algorithm::Sha256_128);
+    assert!(name.matches(&id));
+    assert_eq!(
+        name,
+        NamedInfo::truncated(&id, Algorithm::Sha256_128).unwrap()
+    );
+    assert!(matches!(
+        OkId::try_from(&name),
+        Err(Error::UnsupportedAlgorithm(algorithm)) if algorithm == "sha-256-128"
+    ));
+
+    let other: OkId = "2ːe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        .parse()
+        .unwrap();
+    assert!(!name.matches(&other));
+
+    // The digest length must match the algorithm
+    assert!(matches!(
+        "ni:///sha-256-128;f4OxZX_x_FO5LcGBSKHWXfwtSx-j1ncoSt3SABJtkGk".parse::<NamedInfo>(),
+        Err(Error::InvalidLength)
+    ));
+}
+
+#[test]
+fn test_nih() {
+    let id: OkId = HELLO.parse().unwrap();
+    let name = NamedInfo::truncated(&id, Algorithm::Sha256_120).unwrap();
+    assert_eq!(
+        name.to_nih_uri(),
+        "nih:sha-256-120;7f83-b165-7ff1-fc53-b92d-c181-48a1-d6;8"
+    );
+
+    // Suite ids, other groupings and a missing check digit are all accepted
+    for nih in [
+        "nih:sha-256-120;7f83-b165-7ff1-fc53-b92d-c181-48a1-d6;8",
+        "nih:3;7f83b1-657ff1-fc53b9-2dc181-48a1d6;8",
+        "nih:3;7f83b1657ff1fc53b92dc18148a1d6",
+    ] {
+        assert_eq!(nih.parse::<NamedInfo>().unwrap(), name, "{nih}");
+    }
+
+    // The check digit from RFC 6920 section 7
+    assert!("nih:sha-256-120;5326-9057-e12f-e2b7-4ba0-7c89-2560-a2;f"
+        .parse::<NamedInfo>()
+        .is_ok());
+    assert!("nih:sha-256-120;5326-9057-e12f-e2b7-4ba0-7c89-2560-a2;e"
+        .parse::<NamedInfo>()
+        .is_err());
+
+    let nih = id.to_nih_uri().unwrap();
+    assert_eq!(OkId::from_ni_uri(&nih).unwrap(), id);
+    assert_eq!(OkId::try_from(&url::Url::parse(&nih).unwrap()).unwrap(), id);
+}
+
+#[test]
+fn test_unsupported() {
+    assert!(matches!(
+        OkId::from_ni_uri("ni:///md5;AAAA"),
+        Err(Error::UnsupportedAlgorithm(_))
+    ));
+    assert!(OkId::from_ni_uri("https://example.com/sha-256;AAAA").is_err());
+    let key: OkId = "pːd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        .parse()
+        .unwrap();
+    assert!(matches!(key.to_ni_uri(""), Err(Error::InvalidType)));
+}
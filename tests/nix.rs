@@ -0,0 +1,116 @@
+#![cfg(feature = "nix")]
+use okid::{
+    nix::{from_nix32, nar_hash, store_path_hash, to_nix32, HashFormat},
+    Error, OkId,
+};
+
+const EMPTY: &str = "2ːe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+#[test]
+fn test_nix_hash_formats() {
+    let empty: OkId = EMPTY.parse().unwrap();
+    for (format, expected) in [
+        (
+            HashFormat::Base16,
+            "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ),
+        (
+            HashFormat::Nix32,
+            "sha256:0mdqa9w1p6cmli6976v4wi0sw9r4p5prkj7lzfd1877wk11c9c73",
+        ),
+        (
+            HashFormat::Base64,
+            "sha256:47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=",
+        ),
+        (
+            HashFormat::Sri,
+            "sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=",
+        ),
+    ] {
+        assert_eq!(empty.to_nix_hash(format).unwrap(), expected);
+        assert_eq!(OkId::from_nix_hash(expected).unwrap(), empty);
+    }
+
+    let key: OkId = "pːd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        .parse()
+        .unwrap();
+    assert!(matches!(
+        key.to_nix_hash(HashFormat::Sri),
+        Err(Error::InvalidType)
+    ));
+}
+
+#[test]
+fn test_nix_hash_validation() {
+    assert!(
+        OkId::from_nix_hash("sha256:0mdqa9w1p6cmli6976v4wi0sw9r4p5prkj7lzfd1877wk11c9c7").is_err()
+    );
+    assert!(
+        OkId::from_nix_hash("sha256:0mdqa9w1p6cmli6976v4wi0sw9r4p5prkj7lzfd1877wk11c9c7e").is_err()
+    );
+    assert!(
+        OkId::from_nix_hash("blake3:0mdqa9w1p6cmli6976v4wi0sw9r4p5prkj7lzfd1877wk11c9c73").is_err()
+    );
+    assert!(OkId::from_nix_hash("sha256").is_err());
+    match OkId::from_nix_hash(&format!("sha512:{}", "ab".repeat(64))) {
+        Err(Error::UnsupportedAlgorithm(algorithm)) => assert_eq!(algorithm, "sha512"),
+        other => panic!("expected unsupported algorithm, got {other:?}"),
+    }
+
+    // The top bits of the first digit don't fit in 32 bytes
+    assert!(from_nix32("zmdqa9w1p6cmli6976v4wi0sw9r4p5prkj7lzfd1877wk11c9c73").is_err());
+    assert!(from_nix32("e").is_err());
+    assert_eq!(to_nix32(&from_nix32("0mdqa9w1").unwrap()), "0mdqa9w1");
+}
+
+#[test]
+fn test_store_path_hash() {
+    let fingerprint = "source:sha256:2d711642b726b04401627ca9fbac32f5c8530fb1903cc4db02258717921a4881:/nix/store:hello";
+    let id = store_path_hash(fingerprint);
+    assert_eq!(id.to_string(), "nː1eab18e6c8d6575e3c35d8c5d277f0bf7e89386d");
+    assert_eq!(
+        id.to_nix_store_path_hash().unwrap(),
+        "dlw8jzmzy1vx5ifq6ly5wmynr3k1iaqy"
+    );
+    assert_eq!(id.to_string().parse::<OkId>().unwrap(), id);
+    assert_eq!(
+        OkId::from_nix_store_path("/nix/store/dlw8jzmzy1vx5ifq6ly5wmynr3k1iaqy-hello").unwrap(),
+        id
+    );
+    assert_eq!(
+        OkId::from_nix_store_path("dlw8jzmzy1vx5ifq6ly5wmynr3k1iaqy").unwrap(),
+        id
+    );
+    assert!(OkId::from_nix_store_path("/nix/store/dlw8jzmzy1vx5ifq6ly5wmynr3k1iaq-hello").is_err());
+
+    let empty: OkId = EMPTY.parse().unwrap();
+    assert!(empty.to_nix_store_path_hash().is_err());
+    assert!(id.to_nix_store_hash().is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_nar_hash() {
+    use std::{fs, os::unix::fs::PermissionsExt};
+
+    let root = std::env::temp_dir().join(format!("okid-nix-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("sub")).unwrap();
+    fs::write(root.join("hello.txt"), "hello world\n").unwrap();
+    fs::write(root.join("run.sh"), "#!/bin/sh\n").unwrap();
+    fs::set_permissions(root.join("run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+    fs::write(root.join("sub/f"), "x").unwrap();
+    std::os::unix::fs::symlink("hello.txt", root.join("link")).unwrap();
+
+    let file = nar_hash(root.join("hello.txt")).unwrap();
+    let dir = nar_hash(&root).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+    assert_eq!(
+        file.to_nix_hash(HashFormat::Nix32).unwrap(),
+        "sha256:00zns3gj9hwz2a4b0i07y7nmxybq59lh24bl3xsxblcl6333mjil"
+    );
+    assert_eq!(
+        dir.to_nix_hash(HashFormat::Nix32).unwrap(),
+        "sha256:0mv5bih3j1nwfx7vh2pihmvwdrlsfnxdab7asg1pma18in0r2rjz"
+    );
+}
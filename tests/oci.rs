@@ -0,0 +1,103 @@
+#![cfg(feature = "oci")]
+use std::io::Read;
+
+use okid::{oci::Descriptor, Error, OkId};
+
+const HELLO: &str = "sha256:b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+#[test]
+fn test_oci_digest_roundtrip() {
+    let id = OkId::from_oci_digest(HELLO).unwrap();
+    assert_eq!(
+        id.to_string(),
+        "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+    );
+    assert_eq!(id.to_oci_digest().unwrap(), HELLO);
+
+    #[cfg(feature = "blake3")]
+    {
+        let blake3 = OkId::from(blake3::Hasher::new());
+        let digest = blake3.to_oci_digest().unwrap();
+        assert!(digest.starts_with("blake3:"));
+        assert_eq!(OkId::from_oci_digest(&digest).unwrap(), blake3);
+    }
+}
+
+#[test]
+fn test_oci_digest_validation() {
+    for invalid in [
+        "",
+        "sha256",
+        "sha256:",
+        ":b94d27b9",
+        "SHA256:b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+        "sha256:B94D27B9934D3E08A52E52D7DA7DABFAC484EFE37A5380EE9088F7ACE2EFCDE9",
+        "sha256:b94d27b9",
+        "sha256+:b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+        "sha256:b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde!",
+    ] {
+        assert!(
+            matches!(OkId::from_oci_digest(invalid), Err(Error::InvalidFormat)),
+            "{invalid:?} should be rejected"
+        );
+    }
+
+    // Well formed, but there is no SHA-512 kind
+    let sha512 = format!("sha512:{}", "ab".repeat(64));
+    match OkId::from_oci_digest(&sha512) {
+        Err(Error::UnsupportedAlgorithm(algorithm)) => assert_eq!(algorithm, "sha512"),
+        other => panic!("expected unsupported algorithm, got {other:?}"),
+    }
+    assert!(matches!(
+        OkId::from_oci_digest("multihash+base58:QmRZxt2b1FVZPNqd8hsiykDL3TdBDeTSPX9Kv46HmX4Gx8"),
+        Err(Error::UnsupportedAlgorithm(_))
+    ));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_descriptor_serde() {
+    let json = format!(
+        r#"{{"mediaType":"application/vnd.oci.image.layer.v1.tar","digest":"{HELLO}","size":11,"annotations":{{"a":"b"}}}}"#
+    );
+    let descriptor: Descriptor = serde_json::from_str(&json).unwrap();
+    assert_eq!(descriptor.digest, OkId::from_oci_digest(HELLO).unwrap());
+    assert_eq!(descriptor.size, 11);
+    assert_eq!(
+        serde_json::to_string(&descriptor).unwrap(),
+        format!(
+            r#"{{"mediaType":"application/vnd.oci.image.layer.v1.tar","digest":"{HELLO}","size":11}}"#
+        )
+    );
+
+    let bad = json.replace("sha256:", "sha256:0");
+    assert!(serde_json::from_str::<Descriptor>(&bad).is_err());
+}
+
+#[test]
+fn test_descriptor_streaming_verify() {
+    let descriptor = Descriptor::from_blob("text/plain", b"hello world");
+    assert_eq!(
+        descriptor,
+        Descriptor::new("text/plain", OkId::from_oci_digest(HELLO).unwrap(), 11)
+    );
+
+    // Content passes through unchanged
+    let mut out = String::new();
+    descriptor
+        .verifying_reader(&b"hello world"[..])
+        .unwrap()
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "hello world");
+
+    // Wrong content of the right size
+    let err = descriptor.verify(&b"hello WORLD"[..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    let inner = err.into_inner().unwrap().downcast::<Error>().unwrap();
+    assert!(matches!(*inner, Error::DigestMismatch { .. }));
+
+    // Too long fails before the end, too short at the end
+    assert!(descriptor.verify(&b"hello world, again"[..]).is_err());
+    assert!(descriptor.verify(&b"hello"[..]).is_err());
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "pkarr")]
+use okid::OkId;
+
+const CONTENT: &str = "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+const PREVIOUS: &str = "2ːe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+fn keypair(seed: u8) -> pkarr::Keypair {
+    let secret = OkId::from(&ed25519_dalek::SigningKey::from_bytes(&[seed; 32]));
+    pkarr::Keypair::try_from(secret).unwrap()
+}
+
+#[test]
+fn test_public_key_roundtrip() {
+    let public_key = keypair(1).public_key();
+    let id = OkId::from(public_key.clone());
+    assert_eq!(pkarr::PublicKey::try_from(id).unwrap(), public_key);
+
+    let z32 = id.to_z32().unwrap();
+    assert_eq!(z32, public_key.to_z32());
+    assert_eq!(z32.parse::<OkId>().unwrap(), id);
+    assert_eq!(format!("pk:{z32}").parse::<OkId>().unwrap(), id);
+    assert_eq!(OkId::from_z32(&public_key.to_uri_string()).unwrap(), id);
+
+    let content: OkId = CONTENT.parse().unwrap();
+    assert!(content.to_z32().is_err());
+    assert!(pkarr::PublicKey::try_from(content).is_err());
+    assert!(OkId::from_z32("pk:tooshort").is_err());
+}
+
+#[test]
+fn test_signed_packet_roundtrip() {
+    let signer = keypair(2);
+    let publisher = OkId::from(signer.public_key());
+    let content: OkId = CONTENT.parse().unwrap();
+    let previous: OkId = PREVIOUS.parse().unwrap();
+
+    let packet = okid::pkarr::signed_packet(
+        &signer,
+        [
+            ("latest", content),
+            ("v1.releases", previous),
+            ("@", content),
+        ],
+        300,
+    )
+    .unwrap();
+    let records = okid::pkarr::records(&packet);
+    assert_eq!(records.len(), 3);
+    assert_eq!(records["latest"], content);
+    assert_eq!(records["v1.releases"], previous);
+    assert_eq!(records["@"], content);
+
+    let payload = packet.to_relay_payload();
+    assert_eq!(
+        okid::pkarr::verify_signed_packet(&publisher, &payload).unwrap(),
+        records
+    );
+
+    // Wrong publisher
+    let other = OkId::from(keypair(3).public_key());
+    assert!(okid::pkarr::verify_signed_packet(&other, &payload).is_err());
+
+    // Tampered payload
+    let mut tampered = payload.to_vec();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert!(okid::pkarr::verify_signed_packet(&publisher, &tampered).is_err());
+}
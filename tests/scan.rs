@@ -0,0 +1,73 @@
+use std::io::{BufReader, Cursor};
+
+use okid::{scan, scan_reader, OkId};
+
+const SHA256: &str = "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+const FINGERPRINT: &str = "fː0011223344556677";
+const UUID: &str = "iː2a4b7c3e0f1d48a9b6c5e4d3f2a1b0c9";
+
+fn found(text: &str) -> Vec<&str> {
+    scan(text).map(|(range, _)| &text[range]).collect()
+}
+
+#[test]
+fn test_scan() {
+    let text =
+        format!("[{FINGERPRINT}](https://example.com) see `{SHA256}`,\n- ({UUID}).\n{SHA256}");
+    let ids: Vec<_> = scan(&text).collect();
+    assert_eq!(
+        ids.iter().map(|(_, id)| id.to_string()).collect::<Vec<_>>(),
+        [FINGERPRINT, SHA256, UUID, SHA256]
+    );
+    for (range, id) in ids {
+        assert_eq!(text[range].parse::<OkId>().unwrap(), id);
+    }
+}
+
+#[test]
+fn test_boundaries() {
+    // Word characters on either side, including non-ASCII letters, are not boundaries
+    for text in [
+        format!("x{FINGERPRINT}"),
+        format!("_{FINGERPRINT}"),
+        format!("é{FINGERPRINT}"),
+        format!("{FINGERPRINT}0"),
+        format!("{FINGERPRINT}g"),
+        format!("{FINGERPRINT}_"),
+        format!("{FINGERPRINT}ü"),
+        format!("aː{FINGERPRINT}"),
+        "fː00112233".to_string(),
+        "ː0011223344556677".to_string(),
+        "fːː0011223344556677".to_string(),
+        "f:0011223344556677".to_string(),
+    ] {
+        assert_eq!(found(&text), Vec::<&str>::new(), "{text}");
+    }
+    for text in [
+        format!("é {FINGERPRINT}."),
+        format!("→{FINGERPRINT}→"),
+        format!("/{FINGERPRINT}/"),
+        format!("\"{FINGERPRINT}\""),
+    ] {
+        assert_eq!(found(&text), [FINGERPRINT], "{text}");
+    }
+    assert_eq!(
+        found(&format!("{FINGERPRINT},{FINGERPRINT}")),
+        [FINGERPRINT, FINGERPRINT]
+    );
+}
+
+#[test]
+fn test_scan_reader() {
+    let mut bytes = b"\xff\xfe not utf-8 ".to_vec();
+    bytes.extend(format!("{FINGERPRINT}\r\nnothing here\n\n{UUID} {SHA256}").as_bytes());
+    // A tiny buffer splits ids across reads
+    let found: Vec<_> = scan_reader(BufReader::with_capacity(3, Cursor::new(&bytes)))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(found.len(), 3);
+    for (range, id) in found {
+        let text = std::str::from_utf8(&bytes[range.start as usize..range.end as usize]);
+        assert_eq!(text.unwrap(), id.to_string());
+    }
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "seal")]
+use okid::{
+    seal::{key_id, key_id_with, SealKey},
+    secret::Tags,
+    Error, OkId,
+};
+
+const KEY: [u8; 32] = [7; 32];
+
+fn id() -> OkId {
+    "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn test_seal_roundtrip() {
+    let key = SealKey::from_bytes(42, KEY);
+    let sealed = id().seal(&key).unwrap();
+    assert!(sealed.starts_with('🔒'));
+    assert_eq!(
+        OkId::unseal(std::slice::from_ref(&key), &sealed).unwrap(),
+        id()
+    );
+    assert_eq!(key_id(&sealed), Some(42));
+
+    // Random nonces make every seal different
+    assert_ne!(id().seal(&key).unwrap(), sealed);
+
+    // Unsealed readers, scanners and the plain form do not see the id
+    assert_eq!(OkId::from_display_safe(&sealed), None);
+    assert_eq!(okid::scan_secrets(&sealed).count(), 0);
+    assert!(matches!(
+        OkId::unseal(&[key], &id().to_display_safe()),
+        Err(Error::InvalidFormat)
+    ));
+    assert_eq!(key_id(&id().to_display_safe()), None);
+}
+
+#[test]
+fn test_seal_with_carrier() {
+    let key = SealKey::generate(1).unwrap();
+    let carrier = Tags::new('🔑').unwrap();
+    let sealed = id().seal_with(&key, &carrier).unwrap();
+    assert!(sealed.starts_with('🔑'));
+    assert_eq!(key_id_with(&carrier, &sealed), Some(1));
+    assert_eq!(
+        OkId::unseal_with(std::slice::from_ref(&key), &carrier, &sealed).unwrap(),
+        id()
+    );
+    assert!(OkId::unseal(&[key], &sealed).is_err());
+}
+
+#[test]
+fn test_key_rotation() {
+    let old = SealKey::from_bytes(1, KEY);
+    let new = SealKey::from_bytes(2, [9; 32]);
+    let sealed = id().seal(&old).unwrap();
+    assert_eq!(
+        OkId::unseal(&[new.clone(), old.clone()], &sealed).unwrap(),
+        id()
+    );
+    assert!(matches!(
+        OkId::unseal(&[new], &sealed),
+        Err(Error::UnknownKey(1))
+    ));
+
+    // A different key under the same id, or a tampered key id, fails to authenticate
+    let impostor = SealKey::from_bytes(1, [9; 32]);
+    assert!(matches!(
+        OkId::unseal(&[impostor], &sealed),
+        Err(Error::InvalidSignature(_))
+    ));
+    let mut chars: Vec<char> = sealed.chars().collect();
+    // The emoji, its presentation selector, the version, then the key id
+    chars[6] = if chars[6] == '\u{FE02}' {
+        '\u{FE03}'
+    } else {
+        '\u{FE02}'
+    };
+    let retagged: String = chars.iter().collect();
+    assert_eq!(key_id(&retagged), Some(2));
+    assert!(matches!(
+        OkId::unseal(&[SealKey::from_bytes(2, KEY)], &retagged),
+        Err(Error::InvalidSignature(_))
+    ));
+
+    // Truncated secrets are rejected
+    let truncated: String = sealed.chars().take(20).collect();
+    assert!(matches!(
+        OkId::unseal(&[old], &truncated),
+        Err(Error::InvalidFormat)
+    ));
+}
+
+#[test]
+fn test_seal_key_debug_hides_key() {
+    let key = SealKey::from_bytes(3, KEY);
+    assert_eq!(format!("{key:?}"), "SealKey { id: 3, .. }");
+}
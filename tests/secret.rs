@@ -1,4 +1,7 @@
-use okid::OkId;
+use okid::{
+    secret::{DisplaySafeCarrier, Tags, VariationSelectors},
+    OkId,
+};
 
 #[test]
 fn test_display_safe_roundtrip() {
@@ -40,3 +43,185 @@ fn test_display_safe_with_fingerprint() {
     let decoded = OkId::from_display_safe(&encoded).unwrap();
     assert_eq!(fingerprint_okid, decoded);
 }
+
+#[test]
+fn test_scan_secrets() {
+    let first = "fː0123456789abcdef".parse::<OkId>().unwrap();
+    let second = "iː2a4b7c3e0f1d48a9b6c5e4d3f2a1b0c9"
+        .parse::<OkId>()
+        .unwrap();
+    let text = format!(
+        "🔒 alone, {}{}, and a broken 🔒\u{FE0F}\u{FE01}\u{FE02} one",
+        first.to_display_safe(),
+        second.to_display_safe()
+    );
+    let found: Vec<_> = okid::scan_secrets(&text).collect();
+    assert_eq!(
+        found.iter().map(|(_, id)| *id).collect::<Vec<_>>(),
+        [first, second]
+    );
+    for (range, id) in &found {
+        assert_eq!(OkId::from_display_safe(&text[range.clone()]), Some(*id));
+    }
+
+    assert_eq!(
+        okid::redact_secrets(&text, "[secret]"),
+        "🔒 alone, [secret][secret], and a broken 🔒\u{FE0F}\u{FE01}\u{FE02} one"
+    );
+    assert_eq!(
+        okid::remove_secrets(&text),
+        "🔒 alone, , and a broken 🔒\u{FE0F}\u{FE01}\u{FE02} one"
+    );
+    assert!(matches!(
+        okid::remove_secrets("no secrets"),
+        std::borrow::Cow::Borrowed(_)
+    ));
+}
+
+#[test]
+fn test_from_display_safe_is_strict() {
+    let id = "fː0123456789abcdef".parse::<OkId>().unwrap();
+    let encoded = id.to_display_safe();
+    // The emoji presentation selector is optional
+    assert_eq!(
+        OkId::from_display_safe(&encoded.replacen('\u{FE0F}', "", 1)),
+        Some(id)
+    );
+    for invalid in [
+        format!(" {encoded}"),
+        format!("{encoded}x"),
+        encoded.replacen('\u{FE0F}', "\u{FE0F}x", 1),
+        encoded.chars().take(5).collect(),
+    ] {
+        assert_eq!(OkId::from_display_safe(&invalid), None, "{invalid:?}");
+    }
+}
+
+#[test]
+fn test_scan_secrets_reader() {
+    let id = "fː0123456789abcdef".parse::<OkId>().unwrap();
+    let mut bytes = b"\xff first ".to_vec();
+    bytes.extend(
+        format!(
+            "{}\nsecond: {}\n",
+            id.to_display_safe(),
+            id.to_display_safe()
+        )
+        .as_bytes(),
+    );
+    let found: Vec<_> = okid::scan_secrets_reader(std::io::Cursor::new(&bytes))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(found.len(), 2);
+    for (range, found) in found {
+        let text = std::str::from_utf8(&bytes[range.start as usize..range.end as usize]);
+        assert_eq!(OkId::from_display_safe(text.unwrap()), Some(found));
+    }
+}
+
+fn carriers() -> Vec<(&'static str, Box<dyn DisplaySafeCarrier>)> {
+    vec![
+        ("default", Box::new(VariationSelectors::default())),
+        (
+            "variation selectors",
+            Box::new(
+                VariationSelectors::new('🗝')
+                    .unwrap()
+                    .with_presentation(false),
+            ),
+        ),
+        ("tags", Box::new(Tags::default())),
+        ("tags with key", Box::new(Tags::new('🔑').unwrap())),
+    ]
+}
+
+#[test]
+fn test_carrier_roundtrip() {
+    use unicode_normalization::UnicodeNormalization;
+    let ids = [
+        "fː0123456789abcdef".parse::<OkId>().unwrap(),
+        "iː2a4b7c3e0f1d48a9b6c5e4d3f2a1b0c9".parse().unwrap(),
+        "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+            .parse()
+            .unwrap(),
+    ];
+    for (name, carrier) in carriers() {
+        let decode = |s: &str| OkId::from_display_safe_with(&*carrier, s);
+        for id in ids {
+            let encoded = id.to_display_safe_with(&*carrier);
+            assert_eq!(decode(&encoded), Some(id), "{name}");
+            // Normalisation leaves every carrier intact
+            for normalized in [
+                encoded.nfc().collect::<String>(),
+                encoded.nfkc().collect(),
+                encoded.nfd().collect(),
+                encoded.nfkd().collect(),
+            ] {
+                assert_eq!(normalized, encoded, "{name}");
+            }
+            // Some platforms add an emoji presentation selector
+            let mut presented = encoded.clone();
+            let base = presented.chars().next().unwrap();
+            if !presented[base.len_utf8()..].starts_with('\u{FE0F}') {
+                presented.insert(base.len_utf8(), '\u{FE0F}');
+                assert_eq!(decode(&presented), Some(id), "{name}");
+            }
+            assert_eq!(decode(&encoded[..encoded.len() - 4]), None, "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_tags_survive_stripped_variation_selectors() {
+    use okid::secret::scan_secrets_with;
+    let id = "fː0123456789abcdef".parse::<OkId>().unwrap();
+    let strip = |s: &str| -> String {
+        s.chars()
+            .filter(|c| !matches!(*c as u32, 0xFE00..=0xFE0F | 0xE0100..=0xE01EF))
+            .collect()
+    };
+    assert_eq!(OkId::from_display_safe(&strip(&id.to_display_safe())), None);
+    let encoded = strip(&id.to_display_safe_with(&Tags::default()));
+    assert_eq!(
+        OkId::from_display_safe_with(&Tags::default(), &encoded),
+        Some(id)
+    );
+
+    // Carriers only find their own secrets
+    let text = format!("{} {encoded}", id.to_display_safe());
+    let found: Vec<_> = scan_secrets_with(Tags::default(), &text).collect();
+    assert_eq!(found.len(), 1);
+    assert_eq!(&text[found[0].0.clone()], encoded);
+    assert_eq!(okid::scan_secrets(&text).count(), 1);
+    assert_eq!(
+        okid::secret::redact_secrets_with(Tags::default(), &text, "x"),
+        format!("{} x", id.to_display_safe())
+    );
+}
+
+#[test]
+fn test_carrier_base() {
+    // Changed by NFC, by NFKC, invisible, or part of an encoding
+    for base in [
+        '\u{212B}',
+        'ﬁ',
+        '①',
+        ' ',
+        '\u{200B}',
+        '\u{FE0F}',
+        '\u{E0030}',
+    ] {
+        assert!(VariationSelectors::new(base).is_err(), "{base:?}");
+        assert!(Tags::new(base).is_err(), "{base:?}");
+    }
+    assert!(VariationSelectors::new('★').is_ok());
+}
+
+#[test]
+fn test_default_carrier() {
+    let id = "fː0123456789abcdef".parse::<OkId>().unwrap();
+    assert_eq!(
+        id.to_display_safe(),
+        id.to_display_safe_with(&VariationSelectors::default())
+    );
+}
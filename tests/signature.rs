@@ -0,0 +1,61 @@
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use okid::{signature, OkId};
+
+// RFC 8032 section 7.1, test 1
+const SECRET: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+const PUBLIC: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+const SIGNATURE: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+
+fn rfc8032_key() -> SigningKey {
+    let mut seed = [0u8; 32];
+    hex::decode_to_slice(SECRET, &mut seed).unwrap();
+    SigningKey::from_bytes(&seed)
+}
+
+#[test]
+fn test_sign_matches_rfc8032() {
+    let signing_key = rfc8032_key();
+    let public = OkId::from(&signing_key.verifying_key());
+    assert_eq!(public.to_string(), format!("pː{PUBLIC}"));
+
+    let sig = signature::sign(&signing_key, b"");
+    assert_eq!(sig.to_string(), format!("sː{SIGNATURE}"));
+    assert!(public.verify_signature(b"", &sig).is_ok());
+}
+
+#[test]
+fn test_generate_sign_verify() {
+    let (public, secret) = OkId::generate_keypair().unwrap();
+    let sig = secret.sign(b"artifact").unwrap();
+    assert!(public.verify_signature(b"artifact", &sig).is_ok());
+    assert!(public.verify_signature(b"tampered", &sig).is_err());
+
+    // Only the matching kinds are accepted
+    assert!(secret.verify_signature(b"artifact", &sig).is_err());
+    assert!(public.sign(b"artifact").is_err());
+
+    // The secret survives the display-safe encoding
+    let hidden = secret.to_display_safe();
+    assert_eq!(OkId::from_display_safe(&hidden), Some(secret));
+}
+
+#[test]
+fn test_verifying_key_roundtrip() {
+    let verifying_key = rfc8032_key().verifying_key();
+    let public = OkId::from(&verifying_key);
+    assert_eq!(VerifyingKey::try_from(public).unwrap(), verifying_key);
+}
+
+#[test]
+fn test_signature_parse_and_wireformat() {
+    use jetstream_wireformat::WireFormat;
+
+    let sig: OkId = format!("sː{SIGNATURE}").parse().unwrap();
+    let mut buf = vec![];
+    sig.encode(&mut buf).unwrap();
+    assert_eq!(sig.byte_size(), buf.len() as u32);
+    assert_eq!(sig, OkId::decode(&mut buf.as_slice()).unwrap());
+
+    let parsed = okid::const_parse_okid(&format!("sː{SIGNATURE}")).unwrap();
+    assert_eq!(sig, parsed);
+}
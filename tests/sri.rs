@@ -0,0 +1,84 @@
+#![cfg(feature = "sri")]
+use okid::{
+    sri::{Algorithm, Hash, Integrity},
+    Error, OkId,
+};
+
+const SCRIPT: &[u8] = b"alert('Hello, world.');";
+const SHA256: &str = "sha256-qznLcsROx4GACP2dm0UCKCzCG+HiZ1guq6ZZDob/Tng=";
+const SHA384: &str = "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO";
+const SHA512: &str = "sha512-Q2bFTOhEALkN8hOms2FKTDLy7eugP2zFZ1T8LCvX42Fp3WoNr3bjZSAHeOsHrbV1Fu9/A0EzCinRE7Af1ofPrw==";
+
+#[test]
+fn test_sri_roundtrip() {
+    let id: OkId = "2ːab39cb72c44ec7818008fd9d9b4502282cc21be1e267582eaba6590e86ff4e78"
+        .parse()
+        .unwrap();
+    assert_eq!(id.to_sri().unwrap(), SHA256);
+    assert_eq!(OkId::from_sri(SHA256).unwrap(), id);
+
+    let key: OkId = "pːd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        .parse()
+        .unwrap();
+    assert!(matches!(key.to_sri(), Err(Error::InvalidType)));
+}
+
+#[test]
+fn test_generate() {
+    let integrity = Integrity::generate(
+        SCRIPT,
+        &[Algorithm::Sha256, Algorithm::Sha384, Algorithm::Sha512],
+    );
+    assert_eq!(integrity.to_string(), format!("{SHA256} {SHA384} {SHA512}"));
+    assert_eq!(
+        integrity.to_string().parse::<Integrity>().unwrap(),
+        integrity
+    );
+    assert!(integrity.verify(SCRIPT));
+}
+
+#[test]
+fn test_strongest_algorithm() {
+    // A correct sha256 does not help when the sha512 entry is wrong
+    let wrong512 = Hash::new(Algorithm::Sha512, b"something else").to_string();
+    let integrity: Integrity = format!("{SHA256}\t{wrong512}\n").parse().unwrap();
+    assert!(!integrity.verify(SCRIPT));
+    assert_eq!(
+        integrity
+            .strongest()
+            .map(|h| h.algorithm)
+            .collect::<Vec<_>>(),
+        vec![Algorithm::Sha512]
+    );
+    assert!(matches!(
+        OkId::from_sri(&integrity.to_string()),
+        Err(Error::UnsupportedAlgorithm(algorithm)) if algorithm == "sha512"
+    ));
+
+    // Any entry of the strongest algorithm may match
+    let integrity: Integrity = format!("{wrong512} {SHA512} {SHA256}").parse().unwrap();
+    assert!(integrity.verify(SCRIPT));
+    assert_eq!(integrity.ids(), vec![OkId::from_sri(SHA256).unwrap()]);
+}
+
+#[test]
+fn test_parse() {
+    // Unknown algorithms are skipped and options are kept
+    let integrity: Integrity = format!("md5-AAAA {SHA384}?ct=application/javascript")
+        .parse()
+        .unwrap();
+    assert_eq!(integrity.hashes().len(), 1);
+    assert_eq!(
+        integrity.hashes()[0].options.as_deref(),
+        Some("ct=application/javascript")
+    );
+    assert!(integrity.verify(SCRIPT));
+    assert!(integrity.ids().is_empty());
+
+    for invalid in ["", "  ", "md5-AAAA", "sha256-AAAA", "sha256-!!!", "sha256"] {
+        assert!(
+            invalid.parse::<Integrity>().is_err(),
+            "{invalid:?} should be rejected"
+        );
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "ssh")]
+use okid::{ssh, OkId};
+
+const LINE: &str =
+    "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIILi2uwTjjQM17fB66zrLJc2ZsbtmP7y5maSGYEHh9XJ host@example";
+
+#[test]
+fn test_openssh_line_roundtrip() {
+    let key = OkId::from_openssh(LINE).unwrap();
+    assert_eq!(
+        key.to_string(),
+        "pː82e2daec138e340cd7b7c1ebaceb2c973666c6ed98fef2e6669219810787d5c9"
+    );
+    assert_eq!(key.to_openssh(Some("host@example")).unwrap(), LINE);
+    assert!(OkId::from_openssh("ssh-rsa AAAAB3NzaC1yc2E=").is_err());
+}
+
+#[test]
+fn test_openssh_fingerprints() {
+    let key = OkId::from_openssh(LINE).unwrap();
+    // ssh-keygen -lf
+    let fingerprint = "SHA256:BJKJWaCoBdUmUJzy0qvu3gPBavUfQjDSkFrDFV023/g";
+    assert_eq!(key.to_openssh_fingerprint().unwrap(), fingerprint);
+    assert_eq!(
+        key.to_openssh_md5_fingerprint().unwrap(),
+        "MD5:ec:95:5a:9b:9d:68:f2:c2:9e:ef:8c:59:e4:e7:67:75"
+    );
+
+    let parsed = OkId::from_openssh_fingerprint(fingerprint).unwrap();
+    assert_eq!(parsed, key.ssh_fingerprint().unwrap());
+    assert_eq!(parsed.to_openssh_fingerprint().unwrap(), fingerprint);
+}
+
+#[test]
+fn test_parse_authorized_keys() {
+    let file = format!(
+        "# fleet hosts\n\n{LINE}\nno-pty,command=\"echo hi there\" {LINE}\nssh-rsa AAAAB3NzaC1yc2E= legacy\n"
+    );
+    let entries: Vec<_> = ssh::parse_authorized_keys(&file).collect();
+    assert_eq!(entries.len(), 3);
+
+    let first = entries[0].as_ref().unwrap();
+    assert_eq!(first.options, None);
+    assert_eq!(first.comment.as_deref(), Some("host@example"));
+
+    let second = entries[1].as_ref().unwrap();
+    assert_eq!(
+        second.options.as_deref(),
+        Some("no-pty,command=\"echo hi there\"")
+    );
+    assert_eq!(second.key, first.key);
+
+    assert!(matches!(entries[2], Err(okid::Error::InvalidType)));
+}
@@ -0,0 +1,99 @@
+use okid::{
+    url::{find_all, Route},
+    Error, OkId,
+};
+use url::Url;
+
+const ID: &str = "2ːb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+const ENCODED: &str = "2%CB%90b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+const UUID: &str = "iː2a4b7c3e0f1d48a9b6c5e4d3f2a1b0c9";
+
+fn id() -> OkId {
+    ID.parse().unwrap()
+}
+
+#[test]
+fn test_to_url() {
+    let base = Url::parse("https://example.com/api/").unwrap();
+    for (template, expected) in [
+        (
+            "/files/{okid}/a.txt",
+            format!("https://example.com/files/{ENCODED}/a.txt"),
+        ),
+        ("v1/{okid}", format!("https://example.com/api/v1/{ENCODED}")),
+        (
+            "?id={okid}&x=1",
+            format!("https://example.com/api/?id={ENCODED}&x=1"),
+        ),
+        ("#{okid}", format!("https://example.com/api/#{ENCODED}")),
+    ] {
+        let url = id().to_url(&base, template).unwrap();
+        assert_eq!(url.as_str(), expected);
+        assert_eq!(find_all(&url), vec![id()]);
+    }
+    assert!(matches!(
+        id().to_url(&base, "/files/"),
+        Err(Error::InvalidFormat)
+    ));
+}
+
+#[test]
+fn test_query_and_fragment() {
+    let url = Url::parse(&format!(
+        "https://example.com/?bad=2%CB%90ab&id={ENCODED}#v=1&uuid={UUID}"
+    ))
+    .unwrap();
+    assert_eq!(OkId::from_query(&url, "id").unwrap(), id());
+    assert!(matches!(
+        OkId::from_query(&url, "bad"),
+        Err(Error::InvalidLength)
+    ));
+    assert!(matches!(
+        OkId::from_query(&url, "uuid"),
+        Err(Error::NotFound)
+    ));
+    assert_eq!(
+        OkId::from_fragment(&url, "uuid").unwrap(),
+        UUID.parse().unwrap()
+    );
+    assert_eq!(find_all(&url), vec![id(), UUID.parse().unwrap()]);
+
+    // The url crate decodes form values, and TryFrom still only looks at the path
+    let url = Url::parse_with_params("https://example.com/", [("id", ID)]).unwrap();
+    assert_eq!(OkId::from_query(&url, "id").unwrap(), id());
+    assert!(OkId::try_from(&url).is_err());
+}
+
+#[test]
+fn test_route() {
+    let route = Route::new("/files/{okid}/{name}").unwrap();
+    let matched = route
+        .matches(&format!("/files/{ENCODED}/hello%20world.txt"))
+        .unwrap();
+    assert_eq!(matched.id, id());
+    assert_eq!(matched.param("name"), Some("hello world.txt"));
+
+    // The unencoded form matches too
+    assert!(route.matches(&format!("files/{ID}/x")).is_some());
+    for path in [
+        format!("/blobs/{ENCODED}/x"),
+        format!("/files/{ENCODED}"),
+        format!("/files/{ENCODED}/x/y"),
+        "/files/2%CB%90ab/x".to_string(),
+    ] {
+        assert!(route.matches(&path).is_none(), "{path}");
+    }
+
+    for invalid in [
+        "/files/{name}",
+        "/{okid}/{okid}",
+        "/{okid}/{a}/{a}",
+        "/{okid}/{}",
+        "/{okid}/x{y}",
+    ] {
+        assert!(
+            matches!(Route::new(invalid), Err(Error::InvalidFormat)),
+            "{invalid}"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "x509")]
+use base64::Engine;
+use okid::x509;
+
+// openssl req -new -x509 -key <ed25519 key> -subj "/CN=okid.test"
+const CERT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBPjCB8aADAgECAhR14rpm+o1+gF3aUmx74r9uDZAGCDAFBgMrZXAwFDESMBAG
+A1UEAwwJb2tpZC50ZXN0MCAXDTI2MTAxODE5MDQzM1oYDzIxMjYwOTI0MTkwNDMz
+WjAUMRIwEAYDVQQDDAlva2lkLnRlc3QwKjAFBgMrZXADIQBrxfIMC09gyMpGdlzz
+HA/G9AgrP7yibojoRQhiy+nYUaNTMFEwHQYDVR0OBBYEFMb1HaKB2QYlzPvOrQN7
+g4RpO+57MB8GA1UdIwQYMBaAFMb1HaKB2QYlzPvOrQN7g4RpO+57MA8GA1UdEwEB
+/wQFMAMBAf8wBQYDK2VwA0EApGcKXU4KnwBegAQKv47EQbl0elU68U4doUNcE28Z
+Od6OR26KLcKhXABFfDAcSMUthgl7eOzCLye2KakpxDMABw==
+-----END CERTIFICATE-----
+";
+
+fn cert_der() -> Vec<u8> {
+    let body: String = CERT_PEM
+        .lines()
+        .filter(|l| !l.starts_with("-----"))
+        .collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(body)
+        .unwrap()
+}
+
+#[test]
+fn test_certificate_fingerprint() {
+    // openssl x509 -noout -fingerprint -sha256
+    let expected = "2ː672febf9b37146a4814a8034a163dc82b317ff5444f61dfff5642d93fb0656ae";
+    assert_eq!(
+        x509::certificate_fingerprint(CERT_PEM.as_bytes())
+            .unwrap()
+            .to_string(),
+        expected
+    );
+    assert_eq!(
+        x509::certificate_fingerprint(&cert_der())
+            .unwrap()
+            .to_string(),
+        expected
+    );
+}
+
+#[test]
+fn test_spki_pin() {
+    let pin = x509::spki_pin(CERT_PEM.as_bytes()).unwrap();
+    assert_eq!(
+        pin.to_hpkp_pin().unwrap(),
+        r#"pin-sha256="SOZ1L19kiYdN8uZtFPbahjnIz2KvQ/SVrPWjfSK8wh0=""#
+    );
+
+    let header = format!(
+        r#"{}; pin-sha256="E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g="; max-age=5184000; includeSubDomains"#,
+        pin.to_hpkp_pin().unwrap()
+    );
+    let pins = x509::parse_pins(&header).unwrap();
+    assert_eq!(pins.len(), 2);
+    assert_eq!(pins[0], pin);
+    assert!(x509::parse_pins(r#"pin-sha256="bm90IGEgcGlu""#).is_err());
+}
+
+#[test]
+fn test_certificate_public_key() {
+    let key = x509::certificate_public_key(&cert_der()).unwrap();
+    assert_eq!(
+        key.to_string(),
+        "pː6bc5f20c0b4f60c8ca46765cf31c0fc6f4082b3fbca26e88e8450862cbe9d851"
+    );
+    assert!(x509::certificate_public_key(b"not a certificate").is_err());
+}